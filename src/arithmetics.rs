@@ -1,6 +1,8 @@
 use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
 use crate::ParmesanCloudovo;
 use crate::cloudovo::*;
+use crate::cloudovo::comparison::CmpOp;
+use crate::cloudovo::shifting::RoundMode;
 
 
 // =============================================================================
@@ -44,13 +46,26 @@ pub trait ParmArithmetics {
         k: i64,
     ) -> Self;
 
+    /// Sum: `Σ A_i` (empty sum is zero)
+    fn sum(
+        pc: &ParmesanCloudovo,
+        a: &[Self],
+    ) -> Self where Self: Sized;
+
     /// Scalar multiplication (by an integer): `k·X`
     fn scalar_mul(
         pc: &ParmesanCloudovo,
-        k: i32,
+        k: i64,
         x: &Self,
     ) -> Self;
 
+    /// Multiple-constant multiplication: `k_0·X, k_1·X, ...` (sharing common subexpressions)
+    fn scalar_mul_many(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        ks: &[i64],
+    ) -> Vec<Self> where Self: Sized;
+
     /// Signum: `sgn(X)`
     fn sgn(
         pc: &ParmesanCloudovo,
@@ -64,6 +79,25 @@ pub trait ParmArithmetics {
         y: &Self,
     ) -> Self;
 
+    /// Minimum: `min{X, Y}`
+    fn min(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Absolute value: `|X|`
+    fn abs(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+    ) -> Self;
+
+    /// Argmax: `(i, A_i)`, where `A_i` is the first maximum of `A`
+    fn argmax(
+        pc: &ParmesanCloudovo,
+        a: &[Self],
+    ) -> (Self, Self) where Self: Sized;
+
     /// ReLU: `max{0, X}`
     fn relu(
         pc: &ParmesanCloudovo,
@@ -83,12 +117,180 @@ pub trait ParmArithmetics {
         x: &Self,
     ) -> Self;
 
+    /// Exponentiation by a public exponent: `X^e`
+    fn pow_const(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        e: u32,
+    ) -> Self;
+
+    /// Polynomial evaluation with public coefficients: `Σ c_i·X^i`
+    fn poly_eval(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        coefs: &[i64],
+    ) -> Self;
+
+    /// Division with remainder: `(Q, R)`, such that `X = Q·Y + R` and `|R| < |Y|`
+    /// * `Q` is rounded towards zero, `R` has the sign of `X`
+    /// * division by zero yields `(0, X)`
+    fn div_rem(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> (Self, Self) where Self: Sized;
+
+    /// Quotient: `X / Y` (cf. `div_rem`)
+    fn div(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::div_rem(pc, x, y).0}
+
+    /// Remainder: `X % Y` (cf. `div_rem`)
+    fn rem(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::div_rem(pc, x, y).1}
+
+    /// Division with remainder by a constant: `(Q, R)`, such that `X = Q·d + R` and `0 ≤ R < |d|`
+    fn div_rem_const(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        d: i64,
+    ) -> (Self, Self) where Self: Sized;
+
+    /// Quotient by a constant: `X div d` (cf. `div_rem_const`)
+    fn div_const(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        d: i64,
+    ) -> Self where Self: Sized {Self::div_rem_const(pc, x, d).0}
+
+    /// Remainder by a constant: `X mod d` (cf. `div_rem_const`)
+    fn rem_const(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        d: i64,
+    ) -> Self where Self: Sized {Self::div_rem_const(pc, x, d).1}
+
+    /// Integer square root: `⌊√X⌋` (zero for negative `X`)
+    fn isqrt(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+    ) -> Self;
+
     /// Rounding
     fn round_at(
         pc: &ParmesanCloudovo,
         x: &Self,
         pos: usize,
     ) -> Self;
+
+    /// Left shift: `X · 2^k`
+    fn shl_const(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        k: usize,
+    ) -> Self;
+
+    /// Right shift: `X / 2^k` (rounded by `mode`)
+    fn shr_const(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        k: usize,
+        mode: RoundMode,
+    ) -> Self;
+
+    /// Left shift by a (non-negative) variable: `X · 2^K`
+    fn shl(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        k: &Self,
+    ) -> Self;
+
+    /// Right shift by a (non-negative) variable: `X / 2^K` (rounded by `mode`)
+    fn shr(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        k: &Self,
+        mode: RoundMode,
+    ) -> Self;
+
+    /// Saturation: `min{max{X, min}, max}`
+    fn saturate(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        min: i64,
+        max: i64,
+    ) -> Self;
+
+    /// Comparison: `X op Y` (`1` if true, `0` otherwise)
+    fn cmp(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Comparison with a constant: `X op k` (`1` if true, `0` otherwise)
+    fn cmp_const(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &Self,
+        k: i64,
+    ) -> Self;
+
+    /// Less than: `X < Y`
+    fn lt(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::cmp(pc, CmpOp::LT, x, y)}
+
+    /// Less or equal: `X ≤ Y`
+    fn le(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::cmp(pc, CmpOp::LE, x, y)}
+
+    /// Greater than: `X > Y`
+    fn gt(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::cmp(pc, CmpOp::GT, x, y)}
+
+    /// Greater or equal: `X ≥ Y`
+    fn ge(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::cmp(pc, CmpOp::GE, x, y)}
+
+    /// Equal: `X = Y`
+    fn eq(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::cmp(pc, CmpOp::EQ, x, y)}
+
+    /// Not equal: `X ≠ Y`
+    fn ne(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self where Self: Sized {Self::cmp(pc, CmpOp::NE, x, y)}
+
+    /// Conditional select: `c ? X : Y` (`c` is `1` or `0`)
+    fn select(
+        pc: &ParmesanCloudovo,
+        c: &Self,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
 }
 
 impl ParmArithmetics for i64 {
@@ -114,11 +316,22 @@ impl ParmArithmetics for i64 {
         k: i64,
     ) -> i64 {x + k}
 
+    fn sum(
+        _pc: &ParmesanCloudovo,
+        a: &[i64],
+    ) -> i64 {a.iter().sum()}
+
     fn scalar_mul(
         _pc: &ParmesanCloudovo,
-        k: i32,
+        k: i64,
+        x: &i64,
+    ) -> i64 {k * x}
+
+    fn scalar_mul_many(
+        _pc: &ParmesanCloudovo,
         x: &i64,
-    ) -> i64 {(k as i64) * x}
+        ks: &[i64],
+    ) -> Vec<i64> {ks.iter().map(|k| k * x).collect()}
 
     fn sgn(
         _pc: &ParmesanCloudovo,
@@ -131,6 +344,28 @@ impl ParmArithmetics for i64 {
         y: &i64,
     ) -> i64 {std::cmp::max(*x, *y)}
 
+    fn min(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        y: &i64,
+    ) -> i64 {std::cmp::min(*x, *y)}
+
+    fn abs(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+    ) -> i64 {x.abs()}
+
+    fn argmax(
+        _pc: &ParmesanCloudovo,
+        a: &[i64],
+    ) -> (i64, i64) {
+        let mut im = 0usize;
+        for (i, ai) in a.iter().enumerate() {
+            if *ai > a[im] {im = i;}
+        }
+        (im as i64, a[im])
+    }
+
     fn relu(
         _pc: &ParmesanCloudovo,
         x: &i64,
@@ -147,6 +382,47 @@ impl ParmArithmetics for i64 {
         x: &i64,
     ) -> i64 {x * x}
 
+    fn pow_const(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        e: u32,
+    ) -> i64 {x.pow(e)}
+
+    fn poly_eval(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        coefs: &[i64],
+    ) -> i64 {
+        // Horner's scheme
+        coefs.iter().rev().fold(0, |acc, c| acc * x + c)
+    }
+
+    fn div_rem(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        y: &i64,
+    ) -> (i64, i64) {
+        if *y == 0 {(0, *x)} else {(x.wrapping_div(*y), x.wrapping_rem(*y))}
+    }
+
+    fn div_rem_const(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        d: i64,
+    ) -> (i64, i64) {(x.div_euclid(d), x.rem_euclid(d))}
+
+    fn isqrt(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+    ) -> i64 {
+        if *x <= 0 {return 0;}
+        // fix the floating-point estimate (in i128 to avoid overflows)
+        let mut r = (*x as f64).sqrt() as i128;
+        while r * r > *x as i128 {r -= 1;}
+        while (r + 1) * (r + 1) <= *x as i128 {r += 1;}
+        r as i64
+    }
+
     fn round_at(
         _pc: &ParmesanCloudovo,
         x: &i64,
@@ -163,6 +439,65 @@ impl ParmArithmetics for i64 {
             },
         }
     }
+
+    fn shl_const(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        k: usize,
+    ) -> i64 {x << k}
+
+    fn shr_const(
+        pc: &ParmesanCloudovo,
+        x: &i64,
+        k: usize,
+        mode: RoundMode,
+    ) -> i64 {
+        match mode {
+            RoundMode::FLOOR => x >> k,
+            RoundMode::ROUND => Self::round_at(pc, x, k) >> k,
+        }
+    }
+
+    fn shl(
+        pc: &ParmesanCloudovo,
+        x: &i64,
+        k: &i64,
+    ) -> i64 {Self::shl_const(pc, x, *k as usize)}
+
+    fn shr(
+        pc: &ParmesanCloudovo,
+        x: &i64,
+        k: &i64,
+        mode: RoundMode,
+    ) -> i64 {Self::shr_const(pc, x, *k as usize, mode)}
+
+    fn saturate(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        min: i64,
+        max: i64,
+    ) -> i64 {std::cmp::min(std::cmp::max(*x, min), max)}
+
+    fn cmp(
+        _pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &i64,
+        y: &i64,
+    ) -> i64 {op.eval_plain(*x, *y) as i64}
+
+    fn cmp_const(
+        _pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &i64,
+        k: i64,
+    ) -> i64 {op.eval_plain(*x, k) as i64}
+
+    fn select(
+        _pc: &ParmesanCloudovo,
+        c: &i64,
+        x: &i64,
+        y: &i64,
+    ) -> i64 {if *c != 0 {*x} else {*y}}
 }
 
 impl ParmArithmetics for ParmCiphertext {
@@ -213,9 +548,19 @@ impl ParmArithmetics for ParmCiphertext {
         ).expect("ParmArithmetics::add_const failed.")
     }
 
+    fn sum(
+        pc: &ParmesanCloudovo,
+        a: &[ParmCiphertext],
+    ) -> ParmCiphertext {
+        addition::sum_impl(
+            pc.pub_keys,
+            a,
+        ).expect("ParmArithmetics::sum failed.")
+    }
+
     fn scalar_mul(
         pc: &ParmesanCloudovo,
-        k: i32,
+        k: i64,
         x: &ParmCiphertext,
     ) -> ParmCiphertext {
         scalar_multiplication::scalar_mul_impl(
@@ -225,6 +570,18 @@ impl ParmArithmetics for ParmCiphertext {
         ).expect("ParmArithmetics::scalar_mul failed.")
     }
 
+    fn scalar_mul_many(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        ks: &[i64],
+    ) -> Vec<ParmCiphertext> {
+        scalar_multiplication::scalar_mul_many_impl(
+            pc.pub_keys,
+            x,
+            ks,
+        ).expect("ParmArithmetics::scalar_mul_many failed.")
+    }
+
     fn sgn(
         pc: &ParmesanCloudovo,
         x: &ParmCiphertext,
@@ -249,6 +606,41 @@ impl ParmArithmetics for ParmCiphertext {
         ).expect("ParmArithmetics::max failed.")
     }
 
+    fn min(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        maximum::min_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmArithmetics::min failed.")
+    }
+
+    fn abs(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        maximum::abs_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+        ).expect("ParmArithmetics::abs failed.")
+    }
+
+    fn argmax(
+        pc: &ParmesanCloudovo,
+        a: &[ParmCiphertext],
+    ) -> (ParmCiphertext, ParmCiphertext) {
+        maximum::argmax_impl(
+            pc.params,
+            pc.pub_keys,
+            a,
+        ).expect("ParmArithmetics::argmax failed.")
+    }
+
     fn relu(
         pc: &ParmesanCloudovo,
         x: &ParmCiphertext,
@@ -283,6 +675,68 @@ impl ParmArithmetics for ParmCiphertext {
         ).expect("ParmArithmetics::squ failed.")
     }
 
+    fn pow_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        e: u32,
+    ) -> ParmCiphertext {
+        polynomial::pow_const_impl(
+            pc.pub_keys,
+            x,
+            e,
+        ).expect("ParmArithmetics::pow_const failed.")
+    }
+
+    fn poly_eval(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        coefs: &[i64],
+    ) -> ParmCiphertext {
+        polynomial::poly_eval_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            coefs,
+        ).expect("ParmArithmetics::poly_eval failed.")
+    }
+
+    fn div_rem(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> (ParmCiphertext, ParmCiphertext) {
+        division::div_rem_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmArithmetics::div_rem failed.")
+    }
+
+    fn div_rem_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        d: i64,
+    ) -> (ParmCiphertext, ParmCiphertext) {
+        division::div_rem_const_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            d,
+        ).expect("ParmArithmetics::div_rem_const failed.")
+    }
+
+    fn isqrt(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        square_root::isqrt_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+        ).expect("ParmArithmetics::isqrt failed.")
+    }
+
     fn round_at(
         pc: &ParmesanCloudovo,
         x: &ParmCiphertext,
@@ -295,4 +749,278 @@ impl ParmArithmetics for ParmCiphertext {
             pos,
         ).expect("ParmArithmetics::round_at failed.")
     }
+
+    fn shl_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        k: usize,
+    ) -> ParmCiphertext {
+        shifting::shl_const_impl(
+            pc.pub_keys,
+            x,
+            k,
+        ).expect("ParmArithmetics::shl_const failed.")
+    }
+
+    fn shr_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        k: usize,
+        mode: RoundMode,
+    ) -> ParmCiphertext {
+        shifting::shr_const_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            k,
+            mode,
+        ).expect("ParmArithmetics::shr_const failed.")
+    }
+
+    fn shl(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        k: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        shifting::shl_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            k,
+        ).expect("ParmArithmetics::shl failed.")
+    }
+
+    fn shr(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        k: &ParmCiphertext,
+        mode: RoundMode,
+    ) -> ParmCiphertext {
+        shifting::shr_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            k,
+            mode,
+        ).expect("ParmArithmetics::shr failed.")
+    }
+
+    fn saturate(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        min: i64,
+        max: i64,
+    ) -> ParmCiphertext {
+        width::saturate_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            min,
+            max,
+        ).expect("ParmArithmetics::saturate failed.")
+    }
+
+    fn cmp(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        comparison::cmp_impl(
+            pc.params,
+            pc.pub_keys,
+            op,
+            x,
+            y,
+        ).expect("ParmArithmetics::cmp failed.")
+    }
+
+    fn cmp_const(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &ParmCiphertext,
+        k: i64,
+    ) -> ParmCiphertext {
+        comparison::cmp_const_impl(
+            pc.params,
+            pc.pub_keys,
+            op,
+            x,
+            k,
+        ).expect("ParmArithmetics::cmp_const failed.")
+    }
+
+    fn select(
+        pc: &ParmesanCloudovo,
+        c: &ParmCiphertext,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        selection::select_impl(
+            pc.params,
+            pc.pub_keys,
+            c,
+            x,
+            y,
+        ).expect("ParmArithmetics::select failed.")
+    }
+}
+
+
+// =============================================================================
+//
+//  Parmesan Word Arithmetics
+//
+
+/// Parmesan Word Arithmetics Trait
+/// * operations on words of the integer representation, hence implemented for integers only (not for `ParmFixed`, nor for `f64`)
+pub trait ParmWordArithmetics {
+    /// Bitwise and: `X & Y` (in two's complement)
+    fn bit_and(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Bitwise or: `X | Y` (in two's complement)
+    fn bit_or(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Bitwise xor: `X ^ Y` (in two's complement)
+    fn bit_xor(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Wrapping truncation: `X mod 2^n` in `[-2^(n-1), 2^(n-1))`, where `n = words`
+    fn truncate_wrap(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        words: usize,
+    ) -> Self;
+
+    /// Saturation into `[-2^(n-1), 2^(n-1))`, where `n = words`
+    fn clamp_to_words(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        words: usize,
+    ) -> Self;
+}
+
+impl ParmWordArithmetics for i64 {
+    fn bit_and(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        y: &i64,
+    ) -> i64 {x & y}
+
+    fn bit_or(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        y: &i64,
+    ) -> i64 {x | y}
+
+    fn bit_xor(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        y: &i64,
+    ) -> i64 {x ^ y}
+
+    fn truncate_wrap(
+        _pc: &ParmesanCloudovo,
+        x: &i64,
+        words: usize,
+    ) -> i64 {
+        match words {
+            0 => 0,
+            w if w >= 64 => *x,
+            w => (x << (64 - w)) >> (64 - w),
+        }
+    }
+
+    fn clamp_to_words(
+        pc: &ParmesanCloudovo,
+        x: &i64,
+        words: usize,
+    ) -> i64 {
+        match words {
+            0 => 0,
+            w if w >= 64 => *x,
+            w => ParmArithmetics::saturate(pc, x, -(1i64 << (w - 1)), (1i64 << (w - 1)) - 1),
+        }
+    }
+}
+
+impl ParmWordArithmetics for ParmCiphertext {
+    fn bit_and(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        bitwise::bitwise_impl(
+            pc.params,
+            pc.pub_keys,
+            pbs::AND,
+            x,
+            y,
+        ).expect("ParmWordArithmetics::bit_and failed.")
+    }
+
+    fn bit_or(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        bitwise::bitwise_impl(
+            pc.params,
+            pc.pub_keys,
+            pbs::OR,
+            x,
+            y,
+        ).expect("ParmWordArithmetics::bit_or failed.")
+    }
+
+    fn bit_xor(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        y: &ParmCiphertext,
+    ) -> ParmCiphertext {
+        bitwise::bitwise_impl(
+            pc.params,
+            pc.pub_keys,
+            pbs::XOR,
+            x,
+            y,
+        ).expect("ParmWordArithmetics::bit_xor failed.")
+    }
+
+    fn truncate_wrap(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        words: usize,
+    ) -> ParmCiphertext {
+        width::truncate_wrap_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            words,
+        ).expect("ParmWordArithmetics::truncate_wrap failed.")
+    }
+
+    fn clamp_to_words(
+        pc: &ParmesanCloudovo,
+        x: &ParmCiphertext,
+        words: usize,
+    ) -> ParmCiphertext {
+        width::clamp_to_words_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            words,
+        ).expect("ParmWordArithmetics::clamp_to_words failed.")
+    }
 }
@@ -1,11 +1,55 @@
 use std::error::Error;
+use std::io::{Read,Write};
+use std::ops::{Add,Sub,Neg,Mul,Div,Rem,BitAnd,BitOr,BitXor};
+use std::sync::Arc;
 
 use concrete::{LWE,Encoder};
+use bincode::Options;
+
+use crate::params::Params;
+use crate::arithmetics::{ParmArithmetics, ParmWordArithmetics};
+use crate::cloudovo::comparison::CmpOp;
+use crate::cloudovo::shifting::RoundMode;
+use crate::{ParmesanCloudovo,ParmesanCloudovoShared};
 
 //WISH  ciphertext should be more standalone type: it should hold a reference to its public keys & params so that operations can be done with only this type parameter
 //      ale je to: zasrane, zamrdane
 pub type ParmCiphertext = Vec<LWE>;
 
+/// Encrypted bit in `{0,1}` (cf. `ParmLogic`)
+pub type ParmBool = LWE;
+
+/// Encrypted fixed-point number `ct / 2^scale` (cf. `ParmFixedArithmetics`)
+/// * `scale` is the public count of fractional words
+#[derive(Clone)]
+pub struct ParmFixed {
+    pub ct: ParmCiphertext,
+    pub scale: usize,
+}
+
+/// Encrypted floating-point number `mant · 2^exp` (cf. `ParmFloatArithmetics`)
+/// * both mantissa & exponent are encrypted, their lengths are public
+/// * exponent is kept in `exp.len()`-bit two's complement range
+#[derive(Clone)]
+pub struct ParmFloat {
+    pub mant: ParmCiphertext,
+    pub exp: ParmCiphertext,
+}
+
+/// Magic bytes of serialized `ParmCiphertext`
+pub const PARM_CT_MAGIC: [u8; 4] = *b"PRMC";
+/// Version of `ParmCiphertext` binary format
+pub const PARM_CT_FORMAT_VERSION: u16 = 1;
+/// Maximum count of words of a deserialized `ParmCiphertext` (the input is not trusted)
+pub const PARM_CT_MAX_WORDS: usize = 1 << 16;
+/// Serialized LWE sample holds `dimension + 1` of `u64`'s, plus variance, dimension & encoder
+const LWE_SER_OVERHEAD: usize = 1 << 10;
+
+// word flags of binary format
+const WORD_TRIV_ZERO: u8 = 0;
+const WORD_TRIV:      u8 = 1;
+const WORD_LWE:       u8 = 2;
+
 pub trait ParmCiphertextExt {
     fn triv(
         len: usize,
@@ -15,6 +59,29 @@ pub trait ParmCiphertextExt {
     fn empty() -> ParmCiphertext;
 
     fn single(c: LWE) -> ParmCiphertext;
+
+    fn to_bytes(
+        &self,
+        params: &Params,
+    ) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    fn from_bytes(
+        bytes: &[u8],
+        params: &Params,
+        encoder: &Encoder,
+    ) -> Result<ParmCiphertext, Box<dyn Error>>;
+
+    fn write_to<W: Write>(
+        &self,
+        params: &Params,
+        w: &mut W,
+    ) -> Result<(), Box<dyn Error>>;
+
+    fn read_from<R: Read>(
+        r: &mut R,
+        params: &Params,
+        encoder: &Encoder,
+    ) -> Result<ParmCiphertext, Box<dyn Error>>;
 }
 
 impl ParmCiphertextExt for ParmCiphertext {
@@ -32,4 +99,389 @@ impl ParmCiphertextExt for ParmCiphertext {
     fn single(c: LWE) -> ParmCiphertext {
         vec![c]
     }
+
+    /// Serialize into a byte buffer (cf. `write_to`)
+    fn to_bytes(
+        &self,
+        params: &Params,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        self.write_to(params, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize from a byte buffer (cf. `read_from`)
+    fn from_bytes(
+        bytes: &[u8],
+        params: &Params,
+        encoder: &Encoder,
+    ) -> Result<ParmCiphertext, Box<dyn Error>> {
+        let mut r = bytes;
+        let c = ParmCiphertext::read_from(&mut r, params, encoder)?;
+        if !r.is_empty() {
+            return Err(format!("{} trailing bytes after serialized ParmCiphertext.", r.len()).into());
+        }
+        Ok(c)
+    }
+
+    /// Write in binary format (all numbers little-endian):
+    ///
+    /// ```text
+    /// magic "PRMC" | version: u16 | params fingerprint: u64 | word count: u32 | word_0 | word_1 | ...
+    ///
+    /// word:   0 (u8)                                  .. trivial zero
+    ///         1 (u8) | value: u32                     .. other trivial sample
+    ///         2 (u8) | length: u32 | LWE (bincode)    .. LWE sample
+    /// ```
+    fn write_to<W: Write>(
+        &self,
+        params: &Params,
+        w: &mut W,
+    ) -> Result<(), Box<dyn Error>> {
+        // header
+        w.write_all(&PARM_CT_MAGIC)?;
+        w.write_all(&PARM_CT_FORMAT_VERSION.to_le_bytes())?;
+        w.write_all(&params.fingerprint().to_le_bytes())?;
+        w.write_all(&(self.len() as u32).to_le_bytes())?;
+
+        // words
+        for ci in self {
+            if ci.dimension == 0 && ci.ciphertext.get_body().0 == 0 {
+                w.write_all(&[WORD_TRIV_ZERO])?;
+            } else if ci.dimension == 0 {
+                w.write_all(&[WORD_TRIV])?;
+                w.write_all(&ci.decrypt_uint_triv()?.to_le_bytes())?;
+            } else {
+                let lwe_bytes = bincode::serialize(ci)?;
+                w.write_all(&[WORD_LWE])?;
+                w.write_all(&(lwe_bytes.len() as u32).to_le_bytes())?;
+                w.write_all(&lwe_bytes)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read from binary format (cf. `write_to`)
+    /// * fails for other format version, or if serialized under different params
+    /// * fails if the word count exceeds `PARM_CT_MAX_WORDS`, or if an LWE sample is longer than implied by `params`
+    ///   (nothing is allocated for lengths declared by the input before they are checked)
+    /// * `encoder` is used to re-create trivial samples
+    fn read_from<R: Read>(
+        r: &mut R,
+        params: &Params,
+        encoder: &Encoder,
+    ) -> Result<ParmCiphertext, Box<dyn Error>> {
+        // header
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if magic != PARM_CT_MAGIC {
+            return Err("Not a serialized ParmCiphertext (wrong magic bytes).".into());
+        }
+        let mut ver_b = [0u8; 2];
+        r.read_exact(&mut ver_b)?;
+        let ver = u16::from_le_bytes(ver_b);
+        if ver != PARM_CT_FORMAT_VERSION {
+            return Err(format!("Unsupported ParmCiphertext format version {} (expected {}).", ver, PARM_CT_FORMAT_VERSION).into());
+        }
+        let mut fp_b = [0u8; 8];
+        r.read_exact(&mut fp_b)?;
+        if u64::from_le_bytes(fp_b) != params.fingerprint() {
+            return Err("ParmCiphertext was serialized under different params.".into());
+        }
+        let mut len_b = [0u8; 4];
+        r.read_exact(&mut len_b)?;
+        let len = u32::from_le_bytes(len_b) as usize;
+        if len > PARM_CT_MAX_WORDS {
+            return Err(format!("Serialized ParmCiphertext has too many words ({}, at most {}).", len, PARM_CT_MAX_WORDS).into());
+        }
+        // LWE samples are either key-switched, or right after bootstrapping
+        let max_dim = std::cmp::max(
+            params.lwe_params.dimension,
+            params.rlwe_params.dimension * params.rlwe_params.polynomial_size,
+        );
+        let max_lwe_len = 8 * (max_dim + 1) + LWE_SER_OVERHEAD;
+
+        // words
+        let mut res = ParmCiphertext::empty();
+        for i in 0..len {
+            let mut flag = [0u8; 1];
+            r.read_exact(&mut flag)?;
+            match flag[0] {
+                WORD_TRIV_ZERO => {
+                    res.push(LWE::encrypt_uint_triv(0, encoder)?);
+                },
+                WORD_TRIV => {
+                    let mut m_b = [0u8; 4];
+                    r.read_exact(&mut m_b)?;
+                    res.push(LWE::encrypt_uint_triv(u32::from_le_bytes(m_b), encoder)?);
+                },
+                WORD_LWE => {
+                    let mut lwe_len_b = [0u8; 4];
+                    r.read_exact(&mut lwe_len_b)?;
+                    let lwe_len = u32::from_le_bytes(lwe_len_b) as usize;
+                    if lwe_len > max_lwe_len {
+                        return Err(format!("LWE sample of word {} in serialized ParmCiphertext is too long ({} B, at most {} B).", i, lwe_len, max_lwe_len).into());
+                    }
+                    // the buffer only grows with the data actually read
+                    let mut lwe_bytes: Vec<u8> = Vec::new();
+                    r.by_ref().take(lwe_len as u64).read_to_end(&mut lwe_bytes)?;
+                    if lwe_bytes.len() != lwe_len {
+                        return Err(format!("Unexpected end of serialized ParmCiphertext in word {}.", i).into());
+                    }
+                    // same encoding as bincode::serialize, but nested lengths are limited, too
+                    res.push(bincode::DefaultOptions::new()
+                        .with_fixint_encoding()
+                        .with_limit(lwe_len as u64)
+                        .deserialize(&lwe_bytes)?);
+                },
+                f => {return Err(format!("Unknown flag {} of word {} in serialized ParmCiphertext.", f, i).into());},
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+
+
+// =============================================================================
+//
+//  Encrypted Integer (with cloud context)
+//
+
+/// # Encrypted integer
+/// * holds `ParmCiphertext` together with the cloud context, hence arithmetics
+///   can be written with operators: `&(&a * &b) + &c`, or `a * b + c`
+/// * operands must share the context (i.e., come from clones of the same `ParmesanCloudovoShared`)
+#[derive(Clone)]
+pub struct EncInt {
+    pub ct: ParmCiphertext,
+    pub ctx: ParmesanCloudovoShared,
+}
+
+impl EncInt {
+
+    /// Wrap a ciphertext with a cloud context
+    pub fn new(
+        ct: ParmCiphertext,
+        ctx: &ParmesanCloudovoShared,
+    ) -> EncInt {
+        EncInt {
+            ct,
+            ctx: ctx.clone(),
+        }
+    }
+
+    /// Unwrap the ciphertext
+    pub fn into_ct(self) -> ParmCiphertext {
+        self.ct
+    }
+
+    /// Signum: `sgn(X)`
+    pub fn sgn(&self) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::sgn(pc, x))
+    }
+
+    /// Maximum: `max{X, Y}`
+    pub fn max(&self, other: &EncInt) -> EncInt {
+        self.binop(other, |pc, x, y| ParmArithmetics::max(pc, x, y))
+    }
+
+    /// Minimum: `min{X, Y}`
+    pub fn min(&self, other: &EncInt) -> EncInt {
+        self.binop(other, |pc, x, y| ParmArithmetics::min(pc, x, y))
+    }
+
+    /// Absolute value: `|X|`
+    pub fn abs(&self) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::abs(pc, x))
+    }
+
+    /// ReLU: `max{0, X}`
+    pub fn relu(&self) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::relu(pc, x))
+    }
+
+    /// Squaring: `X^2`
+    pub fn squ(&self) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::squ(pc, x))
+    }
+
+    /// Exponentiation by a public exponent: `X^e`
+    pub fn pow_const(&self, e: u32) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::pow_const(pc, x, e))
+    }
+
+    /// Polynomial evaluation with public coefficients: `Σ c_i·X^i`
+    pub fn poly_eval(&self, coefs: &[i64]) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::poly_eval(pc, x, coefs))
+    }
+
+    /// Division with remainder: `(X / Y, X % Y)` (cf. `ParmArithmetics::div_rem`)
+    pub fn div_rem(&self, other: &EncInt) -> (EncInt, EncInt) {
+        if !Arc::ptr_eq(&self.ctx.pub_keys, &other.ctx.pub_keys) {
+            panic!("EncInt operands do not share the context.");
+        }
+        let (q, r) = self.ctx.with_cloudovo(|pc| ParmArithmetics::div_rem(pc, &self.ct, &other.ct));
+        (EncInt::new(q, &self.ctx), EncInt::new(r, &self.ctx))
+    }
+
+    /// Division by a constant: `X div d` (cf. `ParmArithmetics::div_rem_const`)
+    pub fn div_const(&self, d: i64) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::div_const(pc, x, d))
+    }
+
+    /// Remainder by a constant: `X mod d` (cf. `ParmArithmetics::div_rem_const`)
+    pub fn rem_const(&self, d: i64) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::rem_const(pc, x, d))
+    }
+
+    /// Integer square root: `⌊√X⌋` (zero for negative `X`)
+    pub fn isqrt(&self) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::isqrt(pc, x))
+    }
+
+    /// Add constant: `X + k`
+    pub fn add_const(&self, k: i64) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::add_const(pc, x, k))
+    }
+
+    /// Rounding: `round(X, pos)`
+    pub fn round_at(&self, pos: usize) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::round_at(pc, x, pos))
+    }
+
+    /// Left shift: `X · 2^k`
+    pub fn shl_const(&self, k: usize) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::shl_const(pc, x, k))
+    }
+
+    /// Right shift: `X / 2^k` (rounded by `mode`)
+    pub fn shr_const(&self, k: usize, mode: RoundMode) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::shr_const(pc, x, k, mode))
+    }
+
+    /// Left shift by an encrypted amount: `X · 2^K`
+    pub fn shl(&self, k: &EncInt) -> EncInt {
+        self.binop(k, |pc, x, k| ParmArithmetics::shl(pc, x, k))
+    }
+
+    /// Right shift by an encrypted amount: `X / 2^K` (rounded by `mode`)
+    pub fn shr(&self, k: &EncInt, mode: RoundMode) -> EncInt {
+        self.binop(k, |pc, x, k| ParmArithmetics::shr(pc, x, k, mode))
+    }
+
+    /// Comparison: `X op Y` (encrypted bit)
+    pub fn cmp(&self, op: CmpOp, other: &EncInt) -> EncInt {
+        self.binop(other, |pc, x, y| ParmArithmetics::cmp(pc, op, x, y))
+    }
+
+    /// Comparison with a constant: `X op k` (encrypted bit)
+    pub fn cmp_const(&self, op: CmpOp, k: i64) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::cmp_const(pc, op, x, k))
+    }
+
+    /// Wrapping truncation: `X mod 2^n` in `[-2^(n-1), 2^(n-1))`, where `n = words`
+    pub fn truncate_wrap(&self, words: usize) -> EncInt {
+        self.unop(|pc, x| ParmWordArithmetics::truncate_wrap(pc, x, words))
+    }
+
+    /// Saturation: `min{max{X, min}, max}`
+    pub fn saturate(&self, min: i64, max: i64) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::saturate(pc, x, min, max))
+    }
+
+    /// Saturation into `[-2^(n-1), 2^(n-1))`, where `n = words`
+    pub fn clamp_to_words(&self, words: usize) -> EncInt {
+        self.unop(|pc, x| ParmWordArithmetics::clamp_to_words(pc, x, words))
+    }
+
+    /// Conditional select: `self ? X : Y` (`self` is an encrypted bit)
+    pub fn select(&self, x: &EncInt, y: &EncInt) -> EncInt {
+        if !Arc::ptr_eq(&self.ctx.pub_keys, &y.ctx.pub_keys) {
+            panic!("EncInt operands do not share the context.");
+        }
+        self.binop(x, |pc, c, x| ParmArithmetics::select(pc, c, x, &y.ct))
+    }
+
+    fn unop<F: Fn(&ParmesanCloudovo, &ParmCiphertext) -> ParmCiphertext>(
+        &self,
+        f: F,
+    ) -> EncInt {
+        EncInt {
+            ct: self.ctx.with_cloudovo(|pc| f(pc, &self.ct)),
+            ctx: self.ctx.clone(),
+        }
+    }
+
+    fn binop<F: Fn(&ParmesanCloudovo, &ParmCiphertext, &ParmCiphertext) -> ParmCiphertext>(
+        &self,
+        other: &EncInt,
+        f: F,
+    ) -> EncInt {
+        if !Arc::ptr_eq(&self.ctx.pub_keys, &other.ctx.pub_keys) {
+            panic!("EncInt operands do not share the context.");
+        }
+        EncInt {
+            ct: self.ctx.with_cloudovo(|pc| f(pc, &self.ct, &other.ct)),
+            ctx: self.ctx.clone(),
+        }
+    }
+}
+
+// implement binary operator for all combinations of owned & borrowed operands
+macro_rules! impl_enc_int_binop {
+    ($op:ident, $fn:ident, $parm_fn:path) => {
+        impl $op<&EncInt> for &EncInt {
+            type Output = EncInt;
+            fn $fn(self, other: &EncInt) -> EncInt {
+                self.binop(other, |pc, x, y| $parm_fn(pc, x, y))
+            }
+        }
+        impl $op<EncInt> for &EncInt {
+            type Output = EncInt;
+            fn $fn(self, other: EncInt) -> EncInt { self.$fn(&other) }
+        }
+        impl $op<&EncInt> for EncInt {
+            type Output = EncInt;
+            fn $fn(self, other: &EncInt) -> EncInt { (&self).$fn(other) }
+        }
+        impl $op<EncInt> for EncInt {
+            type Output = EncInt;
+            fn $fn(self, other: EncInt) -> EncInt { (&self).$fn(&other) }
+        }
+    };
+}
+
+impl_enc_int_binop!(Add, add, ParmArithmetics::add);
+impl_enc_int_binop!(Sub, sub, ParmArithmetics::sub);
+impl_enc_int_binop!(Mul, mul, ParmArithmetics::mul);
+impl_enc_int_binop!(Div, div, ParmArithmetics::div);
+impl_enc_int_binop!(Rem, rem, ParmArithmetics::rem);
+impl_enc_int_binop!(BitAnd, bitand, ParmWordArithmetics::bit_and);
+impl_enc_int_binop!(BitOr,  bitor,  ParmWordArithmetics::bit_or);
+impl_enc_int_binop!(BitXor, bitxor, ParmWordArithmetics::bit_xor);
+
+impl Neg for &EncInt {
+    type Output = EncInt;
+    fn neg(self) -> EncInt {
+        self.unop(|_pc, x| ParmArithmetics::opp(x))
+    }
+}
+impl Neg for EncInt {
+    type Output = EncInt;
+    fn neg(self) -> EncInt { -&self }
+}
+
+// scalar multiplication
+impl Mul<i64> for &EncInt {
+    type Output = EncInt;
+    fn mul(self, k: i64) -> EncInt {
+        self.unop(|pc, x| ParmArithmetics::scalar_mul(pc, k, x))
+    }
+}
+impl Mul<i64> for EncInt {
+    type Output = EncInt;
+    fn mul(self, k: i64) -> EncInt { &self * k }
 }
@@ -36,12 +36,45 @@ pub fn add_sub_noise_refresh(
     let mut z = ParmCiphertext::triv(z_noisy.len(), &pub_keys.encoder)?;
 
     z_noisy.par_iter().zip(z.par_iter_mut()).for_each(| (zni, zi) | {
-        *zi = pbs::id__pi_5(pub_keys, zni).expect("pbs::id__pi_5 failed.");
+        *zi = pbs::id(pub_keys, zni).expect("pbs::id failed.");
     });
 
     Ok(z)
 }
 
+/// Sum of many ciphertexts, reduced in a balanced binary tree (with noise refreshal)
+/// * depth is `⌈log n⌉` additions, the halves are reduced in parallel
+/// * empty input yields an empty ciphertext (i.e., zero)
+pub fn sum_impl(
+    pub_keys: &PubKeySet,
+    xs: &[ParmCiphertext],
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    match xs.len() {
+        0 => return Ok(ParmCiphertext::empty()),
+        1 => return Ok(xs[0].clone()),
+        2 => return add_sub_noise_refresh(
+            true,
+            pub_keys,
+            &xs[0],
+            &xs[1],
+        ),
+        _ => {},
+    }
+
+    let (xs_l, xs_r) = xs.split_at(xs.len() / 2);
+    let (s_l, s_r) = rayon::join(
+        || sum_impl(pub_keys, xs_l).expect("sum_impl failed."),
+        || sum_impl(pub_keys, xs_r).expect("sum_impl failed."),
+    );
+
+    add_sub_noise_refresh(
+        true,
+        pub_keys,
+        &s_l,
+        &s_r,
+    )
+}
+
 /// Implementation of parallel addition/subtraction
 pub fn add_sub_impl(
     is_add: bool,
@@ -130,13 +163,12 @@ pub fn add_sub_impl(
             //~ ]);
             // -----------------------------------------------------------------
 
-            let mut q = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
             z = w.clone();
             // one more word for "carry"
             z.push(LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
 
             // this shall not happen
-            if r_triv >= q.len() {
+            if r_triv >= w.len() {
                 println!(">>> add fail:");
                 println!("\tx.len = {}", x.len());
                 println!("\tx_rzero = {}", x_rzero);
@@ -147,13 +179,12 @@ pub fn add_sub_impl(
                 panic!("Unexpected fatal error!");
             }
 
-            q[r_triv..].par_iter_mut().zip(w[r_triv..].par_iter().enumerate()).for_each(| (qi, (i0, wi)) | {
-                let i = i0 + r_triv;
-                // calc   3 w_i + w_i-1
-                let mut wi_3 = wi.mul_uint_constant(3).expect("mul_uint_constant failed.");
-                if i0 > 0 { wi_3.add_uint_inplace(&w[i-1]).expect("add_uint_inplace failed."); }
-                *qi = pbs::f_4__pi_5(pub_keys, &wi_3).expect("f_4__pi_5 failed.");
-            });
+            let mut q = match pub_keys.encoder.nb_bit_precision {
+                3 => calc_q__pi_3(pub_keys, &w, r_triv)?,
+                4 => calc_q__pi_4(pub_keys, &w, r_triv)?,
+                5 | 7 => calc_q__pi_5(pub_keys, &w, r_triv)?,
+                pi => return Err(format!("Addition not implemented for pi = {}.", pi).into()),
+            };
             // q must have the same length as z
             q.push(LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
 
@@ -171,6 +202,90 @@ pub fn add_sub_impl(
     Ok(z)
 }
 
+/// Calculate "carries" q_i such that w_i - 2 q_i + q_{i-1} is in {-1,0,1}
+/// * for pi ≥ 5: q_i = f_4(3 w_i + w_{i-1})
+#[allow(non_snake_case)]
+fn calc_q__pi_5(
+    pub_keys: &PubKeySet,
+    w: &ParmCiphertext,
+    r_triv: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let mut q = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+
+    q[r_triv..].par_iter_mut().zip(w[r_triv..].par_iter().enumerate()).for_each(| (qi, (i0, wi)) | {
+        let i = i0 + r_triv;
+        // calc   3 w_i + w_i-1
+        let mut wi_3 = wi.mul_uint_constant(3).expect("mul_uint_constant failed.");
+        if i0 > 0 { wi_3.add_uint_inplace(&w[i-1]).expect("add_uint_inplace failed."); }
+        *qi = pbs::f_4(pub_keys, &wi_3).expect("pbs::f_4 failed.");
+    });
+
+    Ok(q)
+}
+
+/// Calculate "carries" for pi = 4 (3 w_i + w_{i-1} does not fit)
+/// * t_i = sgn(w_i)
+/// * q_i = f_3(2 w_i + t_{i-1})
+#[allow(non_snake_case)]
+fn calc_q__pi_4(
+    pub_keys: &PubKeySet,
+    w: &ParmCiphertext,
+    r_triv: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let mut t = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+    let mut q = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+
+    t[r_triv..].par_iter_mut().zip(w[r_triv..].par_iter()).for_each(| (ti, wi) | {
+        *ti = pbs::f_1__pi_4__with_val(pub_keys, wi, 1).expect("pbs::f_1__pi_4__with_val failed.");
+    });
+
+    q[r_triv..].par_iter_mut().zip(w[r_triv..].par_iter().enumerate()).for_each(| (qi, (i0, wi)) | {
+        let i = i0 + r_triv;
+        // calc   2 w_i + t_i-1
+        let mut wi_2 = wi.mul_uint_constant(2).expect("mul_uint_constant failed.");
+        if i0 > 0 { wi_2.add_uint_inplace(&t[i-1]).expect("add_uint_inplace failed."); }
+        *qi = pbs::f_3__pi_4(pub_keys, &wi_2).expect("pbs::f_3__pi_4 failed.");
+    });
+
+    Ok(q)
+}
+
+/// Calculate "carries" for pi = 3 (even 2 w_i + t_{i-1} does not fit)
+/// * a_i = [w_i ≡ ±2], b_i = [w_i ≡ ±1], t_i = sgn(w_i)
+/// * q_i = a_i + [b_i + t_{i-1} ≡ ±2]
+#[allow(non_snake_case)]
+fn calc_q__pi_3(
+    pub_keys: &PubKeySet,
+    w: &ParmCiphertext,
+    r_triv: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let mut a = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+    let mut b = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+    let mut t = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+    let mut q = ParmCiphertext::triv(w.len(), &pub_keys.encoder)?;
+
+    a[r_triv..].par_iter_mut()
+        .zip(b[r_triv..].par_iter_mut())
+        .zip(t[r_triv..].par_iter_mut())
+        .zip(w[r_triv..].par_iter())
+        .for_each(| (((ai, bi), ti), wi) | {
+            *ai = pbs::f_2__pi_3(pub_keys, wi).expect("pbs::f_2__pi_3 failed.");
+            *bi = pbs::g_1__pi_3(pub_keys, wi).expect("pbs::g_1__pi_3 failed.");
+            *ti = pbs::f_1__pi_3(pub_keys, wi).expect("pbs::f_1__pi_3 failed.");
+        });
+
+    q[r_triv..].par_iter_mut().zip(b[r_triv..].par_iter().enumerate()).for_each(| (qi, (i0, bi)) | {
+        let i = i0 + r_triv;
+        // calc   b_i + t_i-1
+        let mut bi_t = bi.clone();
+        if i0 > 0 { bi_t.add_uint_inplace(&t[i-1]).expect("add_uint_inplace failed."); }
+        *qi = pbs::f_2__pi_3(pub_keys, &bi_t).expect("pbs::f_2__pi_3 failed.");
+        qi.add_uint_inplace(&a[i]).expect("add_uint_inplace failed.");
+    });
+
+    Ok(q)
+}
+
 pub fn opposite_impl(
     x: &ParmCiphertext,
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
@@ -0,0 +1,82 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use rayon::prelude::*;
+use crossbeam_utils::thread;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use concrete::LWE;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::conversion;
+
+/// Two-input logic gate over encrypted bits (e.g., `pbs::AND`)
+pub type Gate = fn(&PubKeySet, &LWE, &LWE) -> Result<LWE, Box<dyn Error>>;
+
+/// Implementation of a bitwise operation over integers in two's complement (as `&`, `|`, `^` on Rust integers)
+/// * converts both operands into canonical form, applies the gate, and converts back
+pub fn bitwise_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    gate: Gate,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let z: ParmCiphertext;
+
+    measure_duration!(
+        ["Bitwise operation ({}-bit)", std::cmp::max(x.len(), y.len())],
+        [
+            // canonical forms (in parallel)
+            let mut cx = ParmCiphertext::empty();
+            let mut cy = ParmCiphertext::empty();
+            let (cxr, cyr) = (&mut cx, &mut cy);
+            thread::scope(|can_scope| {
+                can_scope.spawn(|_| {*cxr = conversion::to_canonical_impl(params, pub_keys, x).expect("conversion::to_canonical_impl failed.");});
+                can_scope.spawn(|_| {*cyr = conversion::to_canonical_impl(params, pub_keys, y).expect("conversion::to_canonical_impl failed.");});
+            }).expect("thread::scope can_scope failed.");
+
+            let cz = bitwise_canonical_impl(pub_keys, gate, &cx, &cy)?;
+            z = conversion::from_canonical_impl(&cz)?;
+        ]
+    );
+
+    Ok(z)
+}
+
+/// Implementation of a bitwise operation over canonical forms (cf. `conversion::to_canonical_impl`)
+/// * the shorter operand is sign-extended, one bootstrap per bit (all in parallel)
+pub fn bitwise_canonical_impl(
+    pub_keys: &PubKeySet,
+    gate: Gate,
+    cx: &ParmCiphertext,
+    cy: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    if cx.len() == 0 || cy.len() == 0 {
+        return Err("Canonical form must contain at least the sign bit.".into());
+    }
+
+    // sign extension
+    let len = std::cmp::max(cx.len(), cy.len());
+    let mut cxe = cx.clone();
+    let mut cye = cy.clone();
+    cxe.resize(len, cx[cx.len() - 1].clone());
+    cye.resize(len, cy[cy.len() - 1].clone());
+
+    let mut cz = ParmCiphertext::triv(len, &pub_keys.encoder)?;
+    cz.par_iter_mut().zip(cxe.par_iter().zip(cye.par_iter())).for_each(| (czi, (cxi, cyi)) | {
+        *czi = gate(pub_keys, cxi, cyi).expect("gate failed.");
+    });
+
+    Ok(cz)
+}
@@ -0,0 +1,130 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::{pbs,addition,signum};
+
+/// Comparison operator:
+/// * `X < Y`, `X ≤ Y`, `X > Y`, `X ≥ Y`, `X = Y`, `X ≠ Y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CmpOp {
+    LT,
+    LE,
+    GT,
+    GE,
+    EQ,
+    NE,
+}
+
+impl CmpOp {
+    /// Evaluate the comparison on plain integers
+    pub fn eval_plain(&self, x: i64, y: i64) -> bool {
+        match self {
+            CmpOp::LT => x <  y,
+            CmpOp::LE => x <= y,
+            CmpOp::GT => x >  y,
+            CmpOp::GE => x >= y,
+            CmpOp::EQ => x == y,
+            CmpOp::NE => x != y,
+        }
+    }
+
+    /// Evaluate the comparison on the sign of `X - Y`
+    fn eval_sgn(&self, s: i32) -> bool {
+        self.eval_plain(s as i64, 0)
+    }
+}
+
+/// Implementation of comparison `X op Y`, returns an encrypted bit in `{0,1}`
+pub fn cmp_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    op: CmpOp,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+
+    let b: ParmCiphertext;
+
+    measure_duration!(
+        ["Comparison {:?} ({}-bit)", op, std::cmp::max(x.len(), y.len())],
+        [
+            // r = x - y
+            let r: ParmCiphertext = addition::add_sub_impl(
+                false,
+                pub_keys,
+                x,
+                y,
+            )?;
+
+            b = cmp_sgn(params, pub_keys, op, &r)?;
+        ]
+    );
+
+    Ok(b)
+}
+
+/// Implementation of comparison with a public constant `X op k`, returns an encrypted bit in `{0,1}`
+pub fn cmp_const_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    op: CmpOp,
+    x: &ParmCiphertext,
+    k: i64,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+
+    let b: ParmCiphertext;
+
+    measure_duration!(
+        ["Comparison {:?} with constant {} ({}-bit)", op, k, x.len()],
+        [
+            // r = x - k
+            let nk = k.checked_neg().ok_or("Comparison with constant -2^63 not supported.")?;
+            let r: ParmCiphertext = addition::add_const_impl(
+                params,
+                pub_keys,
+                x,
+                nk,
+            )?;
+
+            b = cmp_sgn(params, pub_keys, op, &r)?;
+        ]
+    );
+
+    Ok(b)
+}
+
+/// Evaluate comparison on `R = X - Y`, i.e., on the sign of `R`
+/// * groups by `pi - 2` in the signum recursion, hence the raw sign falls into `(-2^(pi-2), 2^(pi-2))`,
+///   which is narrower than the negacyclic period: any function of the sign fits the final LUT
+fn cmp_sgn(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    op: CmpOp,
+    r: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    if params.bit_precision < 4 {
+        return Err(format!("Comparison not implemented for pi = {}.", params.bit_precision).into());
+    }
+
+    let s_raw: ParmCiphertext = signum::sgn_recursion_raw(
+        params.bit_precision - 2,
+        pub_keys,
+        r,
+    )?;
+
+    let d = (1 << (params.bit_precision - 2)) - 1;
+    let lut = pbs::Lut::new(params.bit_precision, -d..=d, |s| op.eval_sgn(s.signum()) as i32)?;
+
+    Ok(ParmCiphertext::single(pbs::eval_lut(pub_keys, &s_raw[0], &lut)?))
+}
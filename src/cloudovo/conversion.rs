@@ -0,0 +1,98 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use rayon::prelude::*;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::pbs;
+
+/// Implementation of conversion from redundant representation into canonical two's complement
+/// * returns `n + 1` encrypted bits in `{0,1}` (LSB first), the last one is the sign bit (of weight `-2^n`)
+/// * with `L_i` the value of the lowest `i` words, the `i`-th bit is `(x_i - [L_i < 0]) mod 2`,
+///   where signs of all `L_i` are obtained by a parallel prefix scan (Kogge-Stone) in `⌈log n⌉` layers of bootstraps
+pub fn to_canonical_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // the final LUT is not negacyclic over 3 bits
+    if params.bit_precision < 4 {
+        return Err(format!("Conversion to canonical representation not implemented for pi = {}.", params.bit_precision).into());
+    }
+
+    // resolve empty x (i.e., zero)
+    if x.len() == 0 {
+        return Ok(ParmCiphertext::triv(1, &pub_keys.encoder)?);
+    }
+
+    // sign of 2·Hi + Lo, i.e., sign of the most significant non-zero of (Hi, Lo)
+    let lut_sgn = pbs::Lut::new(params.bit_precision, -3..=3, |t| t.signum())?;
+    // (x_i - [s_i < 0]) mod 2 for t = 2·x_i + s_i
+    let lut_bit = pbs::Lut::new(params.bit_precision, -3..=3, |t| match t {-2 | -1 | 2 | 3 => 1, _ => 0})?;
+    // [s < 0]
+    let lut_neg = pbs::Lut::new(params.bit_precision, -1..=1, |s| if s < 0 {1} else {0})?;
+
+    let mut c: ParmCiphertext;
+
+    measure_duration!(
+        ["Conversion to canonical ({}-bit)", x.len()],
+        [
+            // prefix scan: s_i = sgn(L_{i+1})
+            let mut s: ParmCiphertext = x.clone();
+            let mut d = 1usize;
+            while d < x.len() {
+                s = (0..x.len()).into_par_iter().map(|i| {
+                    if i < d {
+                        return s[i].clone();
+                    }
+                    let mut t = s[i].mul_uint_constant(2).expect("mul_uint_constant failed.");
+                    t.add_uint_inplace(&s[i - d]).expect("add_uint_inplace failed.");
+                    pbs::eval_lut(pub_keys, &t, &lut_sgn).expect("pbs::eval_lut failed.")
+                }).collect();
+                d <<= 1;
+            }
+
+            // bits & sign bit
+            c = ParmCiphertext::triv(x.len() + 1, &pub_keys.encoder)?;
+            c.par_iter_mut().enumerate().for_each(| (i, ci) | {
+                if i == x.len() {
+                    *ci = pbs::eval_lut(pub_keys, &s[i - 1], &lut_neg).expect("pbs::eval_lut failed.");
+                    return;
+                }
+                let mut t = x[i].mul_uint_constant(2).expect("mul_uint_constant failed.");
+                if i > 0 {
+                    t.add_uint_inplace(&s[i - 1]).expect("add_uint_inplace failed.");
+                }
+                *ci = pbs::eval_lut(pub_keys, &t, &lut_bit).expect("pbs::eval_lut failed.");
+            });
+        ]
+    );
+
+    Ok(c)
+}
+
+/// Implementation of conversion from canonical two's complement (cf. `to_canonical_impl`) into redundant representation
+/// * only the sign bit changes its weight (from `2^n` to `-2^n`), no bootstrapping needed
+pub fn from_canonical_impl(
+    c: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let mut x = c.clone();
+
+    if let Some(sign) = x.last_mut() {
+        *sign = sign.opposite_uint()?;
+    }
+
+    Ok(x)
+}
+
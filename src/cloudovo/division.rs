@@ -0,0 +1,249 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use crossbeam_utils::thread;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use concrete::LWE;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::{pbs,addition,scalar_multiplication,signum,comparison,maximum,rounding,selection,width};
+use super::comparison::CmpOp;
+
+/// Implementation of integer division with remainder: `(Q, R)` such that `X = Q·Y + R`
+/// * truncating semantics (as `/` and `%` on Rust integers): `Q` is rounded towards zero, `R` has the sign of `X`
+/// * division by zero yields `Q = 0`, `R = X`
+/// * restoring division of `|X|` by `|Y|`, one signum per word of `X`
+pub fn div_rem_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<(ParmCiphertext, ParmCiphertext), Box<dyn Error>> {
+    // relu_plus does not exist for pi = 3
+    if params.bit_precision < 4 {
+        return Err(format!("Division not implemented for pi = {}.", params.bit_precision).into());
+    }
+
+    // resolve trivial cases (empty ciphertext is zero)
+    if x.len() == 0 {
+        return Ok((ParmCiphertext::empty(), ParmCiphertext::empty()));
+    }
+    if y.len() == 0 {
+        return Ok((ParmCiphertext::empty(), x.clone()));
+    }
+
+    let q: ParmCiphertext;
+    let r: ParmCiphertext;
+
+    measure_duration!(
+        ["Division ({}-bit by {}-bit)", x.len(), y.len()],
+        [
+            // absolute values & signs (in parallel)
+            let mut ax = ParmCiphertext::empty();
+            let mut ay = ParmCiphertext::empty();
+            let mut nx = ParmCiphertext::empty();
+            let mut ny = ParmCiphertext::empty();
+            let mut zy = ParmCiphertext::empty();
+            let (axr, ayr, nxr, nyr, zyr) = (&mut ax, &mut ay, &mut nx, &mut ny, &mut zy);
+
+            thread::scope(|abs_scope| {
+                abs_scope.spawn(|_| {*axr = maximum::abs_impl(params, pub_keys, x).expect("maximum::abs_impl failed.");});
+                abs_scope.spawn(|_| {*ayr = maximum::abs_impl(params, pub_keys, y).expect("maximum::abs_impl failed.");});
+                abs_scope.spawn(|_| {*nxr = comparison::cmp_const_impl(params, pub_keys, CmpOp::LT, x, 0).expect("comparison::cmp_const_impl failed.");});
+                abs_scope.spawn(|_| {*nyr = comparison::cmp_const_impl(params, pub_keys, CmpOp::LT, y, 0).expect("comparison::cmp_const_impl failed.");});
+                abs_scope.spawn(|_| {*zyr = comparison::cmp_const_impl(params, pub_keys, CmpOp::EQ, y, 0).expect("comparison::cmp_const_impl failed.");});
+            }).expect("thread::scope abs_scope failed.");
+
+            // |Q|, |R|
+            let (aq, ar) = restoring_div(params, pub_keys, &ax, &ay)?;
+
+            // sign of Q: nx XOR ny
+            let xor = pbs::Lut::new(params.bit_precision, 0..=2, |s| s & 1)?;
+            let nq = ParmCiphertext::single(pbs::eval_lut(pub_keys, &nx[0].add_uint(&ny[0])?, &xor)?);
+
+            // fix signs (n.b., for Y = 0, |R| is not valid, cf. restoring_div, hence R = X is selected)
+            let sq = selection::select_impl(params, pub_keys, &nq, &addition::opposite_impl(&aq)?, &aq)?;
+            let sr = selection::select_impl(params, pub_keys, &nx, &addition::opposite_impl(&ar)?, &ar)?;
+            q      = selection::select_impl(params, pub_keys, &zy, &ParmCiphertext::empty(), &sq)?;
+            r      = selection::select_impl(params, pub_keys, &zy, x, &sr)?;
+        ]
+    );
+
+    Ok((q, r))
+}
+
+/// Restoring division of non-negative `A` by non-negative `B`
+/// * for `i = n-1 .. 0`: `T = R - B·2^i`, if `T ≥ 0` then `R = T` and `q_i = 1`
+/// * `R < B·2^(i+1)` holds after each step, since `A < 2^n`
+/// * after step `i`, `0 ≤ R < B·2^i < 2^(m+i)`, where `m` is the length of `B`, hence `R` is trimmed to `m + i + 1` words
+///   (otherwise, it would grow by a carry word in each step); n.b., this does not hold for `B = 0`, for which `R` is not valid
+fn restoring_div(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    a: &ParmCiphertext,
+    b: &ParmCiphertext,
+) -> Result<(ParmCiphertext, ParmCiphertext), Box<dyn Error>> {
+    let mut q = ParmCiphertext::triv(a.len(), &pub_keys.encoder)?;
+    let mut r = a.clone();
+
+    // raw sign falls into (-2^(pi-2), 2^(pi-2)), cf. comparison
+    let d = (1 << (params.bit_precision - 2)) - 1;
+    let lut_s_2 = pbs::Lut::new(params.bit_precision, -d..=d, |s| if s >= 0 {2} else {-2})?;
+    let lut_q   = pbs::Lut::new(params.bit_precision, -d..=d, |s| if s >= 0 {1} else {0})?;
+
+    for i in (0..a.len()).rev() {
+        // B·2^i
+        let mut bi = ParmCiphertext::triv(i, &pub_keys.encoder)?;
+        bi.extend(b.iter().cloned());
+
+        // T = R - B·2^i
+        let t: ParmCiphertext = addition::add_sub_impl(
+            false,
+            pub_keys,
+            &r,
+            &bi,
+        )?;
+
+        let s_raw: ParmCiphertext = signum::sgn_recursion_raw(
+            params.bit_precision - 2,
+            pub_keys,
+            &t,
+        )?;
+
+        // s_2 & q_i (in parallel)
+        let mut s_2 = LWE::encrypt_uint_triv(0, &pub_keys.encoder)?;
+        let s_2r = &mut s_2;
+        let qir = &mut q[i];
+        thread::scope(|sq_scope| {
+            sq_scope.spawn(|_| {
+                *s_2r = pbs::eval_lut(pub_keys, &s_raw[0], &lut_s_2).expect("pbs::eval_lut failed.");
+            });
+            sq_scope.spawn(|_| {
+                *qir  = pbs::eval_lut(pub_keys, &s_raw[0], &lut_q  ).expect("pbs::eval_lut failed.");
+            });
+        }).expect("thread::scope sq_scope failed.");
+
+        // R = T ≥ 0 ? T : R
+        let r_sel = selection::select_by_s2(
+            params,
+            pub_keys,
+            &s_2,
+            &t,
+            &r,
+        )?;
+
+        // the remainder is trimmed to its bound (it is returned after the last step)
+        r = width::trim_nonneg_impl(
+            params,
+            pub_keys,
+            &r_sel,
+            b.len() + i,
+        )?;
+    }
+
+    Ok((q, r))
+}
+
+/// Implementation of division with remainder by a public constant: `(Q, R)` such that `X = Q·d + R`
+/// * Euclidean semantics: `0 ≤ R < |d|` (i.e., for `d > 0`, `Q` is rounded towards `-∞`)
+/// * `Q0 = round(X·M / 2^(n+1))` with `M = round(2^(n+1) / |d|)` is either `⌊X / |d|⌋`, or one more,
+///   which is corrected by the sign of `R0 = X - Q0·|d|`
+pub fn div_rem_const_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    d: i64,
+) -> Result<(ParmCiphertext, ParmCiphertext), Box<dyn Error>> {
+    // resolve trivial cases
+    if d == 0 {
+        return Err("Division by zero constant.".into());
+    }
+    if x.len() == 0 {
+        return Ok((ParmCiphertext::empty(), ParmCiphertext::empty()));
+    }
+
+    let d_abs = d.checked_abs().ok_or("Division by constant -2^63 not supported.")?;
+
+    // |d| == 1
+    if d_abs == 1 {
+        let q = if d > 0 {x.clone()} else {addition::opposite_impl(x)?};
+        return Ok((q, ParmCiphertext::empty()));
+    }
+
+    // scale of the reciprocal
+    let s = x.len() + 1;
+    let m_rcp = if s < 127 {((1i128 << s) + (d_abs as i128) / 2) / (d_abs as i128)} else {i128::MAX};
+    if m_rcp > i64::MAX as i128 {
+        return Err(format!("Division of {}-bit ciphertext by constant {} not supported (reciprocal does not fit i64).", x.len(), d).into());
+    }
+
+    let q: ParmCiphertext;
+    let r: ParmCiphertext;
+
+    measure_duration!(
+        ["Division by constant {} ({}-bit)", d, x.len()],
+        [
+            // Q0 = round(X·M / 2^s)
+            let xm: ParmCiphertext = scalar_multiplication::scalar_mul_impl(
+                pub_keys,
+                m_rcp as i64,
+                x,
+            )?;
+            let xm_r: ParmCiphertext = rounding::round_at_impl(
+                params,
+                pub_keys,
+                &xm,
+                s,
+            )?;
+            let q0: ParmCiphertext = if xm_r.len() > s {xm_r[s..].to_vec()} else {ParmCiphertext::empty()};
+
+            // R0 = X - Q0·|d|
+            let r0: ParmCiphertext = addition::add_sub_impl(
+                false,
+                pub_keys,
+                x,
+                &scalar_multiplication::scalar_mul_impl(pub_keys, d_abs, &q0)?,
+            )?;
+
+            // c = R0 < 0
+            let c: ParmCiphertext = comparison::cmp_const_impl(
+                params,
+                pub_keys,
+                CmpOp::LT,
+                &r0,
+                0,
+            )?;
+
+            // Q = Q0 - c, R = R0 + c·|d| (in parallel)
+            let mut qa = ParmCiphertext::empty();
+            let mut ra = ParmCiphertext::empty();
+            let (qar, rar) = (&mut qa, &mut ra);
+            thread::scope(|qr_scope| {
+                qr_scope.spawn(|_| {
+                    *qar = addition::add_sub_impl(false, pub_keys, &q0, &c).expect("addition::add_sub_impl failed.");
+                });
+                qr_scope.spawn(|_| {
+                    let cd = scalar_multiplication::scalar_mul_impl(pub_keys, d_abs, &c).expect("scalar_multiplication::scalar_mul_impl failed.");
+                    *rar = addition::add_sub_impl(true, pub_keys, &r0, &cd).expect("addition::add_sub_impl failed.");
+                });
+            }).expect("thread::scope qr_scope failed.");
+
+            // X = Q·|d| + R = (-Q)·d + R
+            q = if d > 0 {qa} else {addition::opposite_impl(&qa)?};
+            r = ra;
+        ]
+    );
+
+    Ok((q, r))
+}
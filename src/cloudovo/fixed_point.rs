@@ -0,0 +1,249 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmFixed};
+use super::{addition,multiplication,squaring,polynomial,division,square_root,shifting};
+use super::shifting::RoundMode;
+
+/// Implementation of rescaling of a fixed-point number to `scale` fractional words
+/// * increasing the scale is free (words are prepended), decreasing rounds to the nearest (cf. `round_at`)
+pub fn rescale_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    scale: usize,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let ct = if scale >= x.scale {
+        shifting::shl_const_impl(
+            pub_keys,
+            &x.ct,
+            scale - x.scale,
+        )?
+    } else {
+        shifting::shr_const_impl(
+            params,
+            pub_keys,
+            &x.ct,
+            x.scale - scale,
+            RoundMode::ROUND,
+        )?
+    };
+
+    Ok(ParmFixed {ct, scale})
+}
+
+/// Implementation of alignment of fixed-point numbers to their greatest scale (which is returned, too)
+/// * only prepends words, hence it is free & exact
+pub fn align_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    xs: &[&ParmFixed],
+) -> Result<(Vec<ParmCiphertext>, usize), Box<dyn Error>> {
+    let scale = xs.iter().map(|x| x.scale).max().unwrap_or(0);
+
+    let mut cts: Vec<ParmCiphertext> = Vec::new();
+    for x in xs.iter() {
+        cts.push(rescale_impl(params, pub_keys, x, scale)?.ct);
+    }
+
+    Ok((cts, scale))
+}
+
+/// Integer constant at given scale: `k · 2^scale`
+/// * fails if it does not fit `i64`
+pub fn const_at_scale(
+    k: i64,
+    scale: usize,
+) -> Result<i64, Box<dyn Error>> {
+    if k == 0 {
+        return Ok(0);
+    }
+    if scale >= 63 {
+        return Err(format!("Constant {} at scale {} does not fit i64.", k, scale).into());
+    }
+    k.checked_mul(1i64 << scale).ok_or_else(|| format!("Constant {} at scale {} does not fit i64.", k, scale).into())
+}
+
+/// Implementation of fixed-point addition of an integer constant: `X + k`
+pub fn add_const_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    k: i64,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let ct = addition::add_const_impl(
+        params,
+        pub_keys,
+        &x.ct,
+        const_at_scale(k, x.scale)?,
+    )?;
+
+    Ok(ParmFixed {ct, scale: x.scale})
+}
+
+/// Implementation of fixed-point addition/subtraction
+/// * operands are aligned to the greater scale, hence the result is exact
+pub fn add_sub_impl(
+    is_add: bool,
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    y: &ParmFixed,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let scale = std::cmp::max(x.scale, y.scale);
+
+    let xa = rescale_impl(params, pub_keys, x, scale)?;
+    let ya = rescale_impl(params, pub_keys, y, scale)?;
+
+    let ct: ParmCiphertext = addition::add_sub_impl(
+        is_add,
+        pub_keys,
+        &xa.ct,
+        &ya.ct,
+    )?;
+
+    Ok(ParmFixed {ct, scale})
+}
+
+/// Implementation of fixed-point multiplication
+/// * the exact product has scale `x.scale + y.scale`, it is rounded back to the greater of the two
+pub fn mul_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    y: &ParmFixed,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let res: ParmFixed;
+
+    measure_duration!(
+        ["Fixed-point multiplication ({}-bit, scale {} × {}-bit, scale {})", x.ct.len(), x.scale, y.ct.len(), y.scale],
+        [
+            let p = ParmFixed {
+                ct: multiplication::mul_impl(
+                    pub_keys,
+                    &x.ct,
+                    &y.ct,
+                )?,
+                scale: x.scale + y.scale,
+            };
+
+            res = rescale_impl(
+                params,
+                pub_keys,
+                &p,
+                std::cmp::max(x.scale, y.scale),
+            )?;
+        ]
+    );
+
+    Ok(res)
+}
+
+/// Implementation of fixed-point squaring (rounded back to the scale of `X`)
+pub fn squ_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let p = ParmFixed {
+        ct: squaring::squ_impl(
+            pub_keys,
+            &x.ct,
+        )?,
+        scale: 2 * x.scale,
+    };
+
+    rescale_impl(params, pub_keys, &p, x.scale)
+}
+
+/// Implementation of fixed-point exponentiation by a public exponent: `X^e`
+/// * the exact power has scale `e · x.scale`, it is rounded back (once) to the scale of `X`
+pub fn pow_const_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    e: u32,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let p = ParmFixed {
+        ct: polynomial::pow_const_impl(
+            pub_keys,
+            &x.ct,
+            e,
+        )?,
+        scale: (e as usize) * x.scale,
+    };
+
+    rescale_impl(params, pub_keys, &p, x.scale)
+}
+
+/// Implementation of fixed-point division with remainder: `(Q, R)`, such that `X = Q·Y + R`
+/// * `Q` is an integer rounded towards zero (scale 0), `R` has the greater scale of the two (and the sign of `X`)
+pub fn div_rem_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    y: &ParmFixed,
+) -> Result<(ParmFixed, ParmFixed), Box<dyn Error>> {
+    let (xy, scale) = align_impl(params, pub_keys, &[x, y])?;
+
+    let (q, r) = division::div_rem_impl(
+        params,
+        pub_keys,
+        &xy[0],
+        &xy[1],
+    )?;
+
+    Ok((ParmFixed {ct: q, scale: 0}, ParmFixed {ct: r, scale}))
+}
+
+/// Implementation of fixed-point division with remainder by an integer constant: `(Q, R)`, such that `X = Q·d + R`
+/// * `Q` is an integer (scale 0), `0 ≤ R < |d|` has the scale of `X`
+pub fn div_rem_const_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+    d: i64,
+) -> Result<(ParmFixed, ParmFixed), Box<dyn Error>> {
+    let (q, r) = division::div_rem_const_impl(
+        params,
+        pub_keys,
+        &x.ct,
+        const_at_scale(d, x.scale)?,
+    )?;
+
+    Ok((ParmFixed {ct: q, scale: 0}, ParmFixed {ct: r, scale: x.scale}))
+}
+
+/// Implementation of fixed-point integer square root: `⌊√X⌋` (scale 0)
+/// * `⌊√X⌋ = ⌊√⌊X⌋⌋`, hence the fractional words are dropped first
+pub fn isqrt_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFixed,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let x_int = shifting::shr_const_impl(
+        params,
+        pub_keys,
+        &x.ct,
+        x.scale,
+        RoundMode::FLOOR,
+    )?;
+
+    let ct = square_root::isqrt_impl(
+        params,
+        pub_keys,
+        &x_int,
+    )?;
+
+    Ok(ParmFixed {ct, scale: 0})
+}
@@ -0,0 +1,224 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use crossbeam_utils::thread;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use concrete::LWE;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt, ParmFloat};
+use super::{addition,multiplication,comparison,maximum,rounding,selection,shifting,conversion,width};
+use super::comparison::CmpOp;
+
+/// Implementation of floating-point multiplication
+/// * mantissas are multiplied, exponents added, then the product is normalized (cf. `normalize_impl`)
+///   into the greater of the mantissa & exponent lengths of the operands
+pub fn mul_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmFloat,
+    y: &ParmFloat,
+) -> Result<ParmFloat, Box<dyn Error>> {
+    let mant_len = std::cmp::max(x.mant.len(), y.mant.len());
+    let exp_len  = std::cmp::max(x.exp.len(),  y.exp.len());
+
+    // resolve zero-length mantissas
+    if x.mant.len() == 0 || y.mant.len() == 0 {
+        return Ok(ParmFloat {
+            mant: ParmCiphertext::triv(mant_len, &pub_keys.encoder)?,
+            exp:  ParmCiphertext::triv(exp_len,  &pub_keys.encoder)?,
+        });
+    }
+
+    let res: ParmFloat;
+
+    measure_duration!(
+        ["Floating-point multiplication ({}-bit mantissa, {}-bit exponent)", mant_len, exp_len],
+        [
+            // M = Mx × My, E = Ex + Ey (in parallel)
+            let mut m = ParmCiphertext::empty();
+            let mut e = ParmCiphertext::empty();
+            let (mr, er) = (&mut m, &mut e);
+            thread::scope(|me_scope| {
+                me_scope.spawn(|_| {
+                    *mr = multiplication::mul_impl(pub_keys, &x.mant, &y.mant).expect("multiplication::mul_impl failed.");
+                });
+                me_scope.spawn(|_| {
+                    *er = addition::add_sub_impl(true, pub_keys, &x.exp, &y.exp).expect("addition::add_sub_impl failed.");
+                });
+            }).expect("thread::scope me_scope failed.");
+
+            res = normalize_impl(
+                params,
+                pub_keys,
+                &m,
+                &e,
+                mant_len,
+                exp_len,
+            )?;
+        ]
+    );
+
+    Ok(res)
+}
+
+/// Implementation of normalization of `M · 2^E` into a mantissa of `mant_len` words & an exponent of `exp_len` words
+/// * the leading position is searched in canonical binary representation of `|M|` (in redundant representation,
+///   a leading `±1` may be followed by opposite words, i.e., a non-zero top word tells nothing about magnitude)
+/// * leading zero bits are detected in a binary manner: for `j = ⌊log(n-1)⌋ .. 0`,
+///   if the top `2^j` bits are zero (i.e., their signum is zero), the bits are shifted by `2^j`
+/// * then the bits are rounded; the rounding carry may only give exactly `2^mant_len`, which is detected
+///   by another canonical conversion & resolved by setting the mantissa to `2^(mant_len-1)` (i.e., a conditional shift)
+/// * finally, the sign of `M` is applied, hence `2^(mant_len-1) ≤ |mant| < 2^mant_len` (unless `M = 0`)
+/// * the exponent is saturated into `exp_len`-bit two's complement (on underflow, the result is not flushed to zero)
+pub fn normalize_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    m: &ParmCiphertext,
+    e: &ParmCiphertext,
+    mant_len: usize,
+    exp_len: usize,
+) -> Result<ParmFloat, Box<dyn Error>> {
+    // the final LUT of signum & select
+    if params.bit_precision < 4 {
+        return Err(format!("Floating-point normalization not implemented for pi = {}.", params.bit_precision).into());
+    }
+    if mant_len == 0 || exp_len == 0 || exp_len > 63 {
+        return Err(format!("Floating-point normalization into {}-word mantissa & {}-word exponent not supported.", mant_len, exp_len).into());
+    }
+
+    // pad short mantissas from the right (shifts them left by a public amount)
+    let pad = if m.len() < mant_len {mant_len - m.len()} else {0};
+    let mut x = shifting::shl_const_impl(pub_keys, m, pad)?;
+    if x.len() < mant_len {
+        // m is empty
+        x = ParmCiphertext::triv(mant_len, &pub_keys.encoder)?;
+    }
+    let n = x.len();
+
+    // |X| and [X < 0] (in parallel)
+    let mut a = ParmCiphertext::empty();
+    let mut neg = ParmCiphertext::empty();
+    let (ar, negr) = (&mut a, &mut neg);
+    let xr = &x;
+    thread::scope(|an_scope| {
+        an_scope.spawn(|_| {
+            *ar = maximum::abs_impl(params, pub_keys, xr).expect("maximum::abs_impl failed.");
+        });
+        an_scope.spawn(|_| {
+            *negr = comparison::cmp_const_impl(params, pub_keys, CmpOp::LT, xr, 0).expect("comparison::cmp_const_impl failed.");
+        });
+    }).expect("thread::scope an_scope failed.");
+
+    // canonical bits of |X| (n.b., |X| < 2^n, the sign bit is zero)
+    let mut b = conversion::to_canonical_impl(
+        params,
+        pub_keys,
+        &a,
+    )?;
+    b.truncate(n);
+
+    // binary search for leading zeros: Z = Σ z_j·2^j
+    let mut z: Vec<ParmCiphertext> = Vec::new();
+    let js: Vec<usize> = (0..usize::BITS as usize).filter(|j| (1usize << j) < n).collect();
+    for j in js.iter().rev() {
+        let w = 1usize << j;
+
+        // z_j = [top 2^j bits are zero]
+        let zj = comparison::cmp_impl(
+            params,
+            pub_keys,
+            CmpOp::EQ,
+            &b[n-w..].to_vec(),
+            &ParmCiphertext::empty(),
+        )?;
+
+        // B = z_j ? B << 2^j : B
+        let mut b_sh = ParmCiphertext::triv(w, &pub_keys.encoder)?;
+        b_sh.extend(b[..n-w].iter().cloned());
+        b = selection::select_impl(
+            params,
+            pub_keys,
+            &zj,
+            &b_sh,
+            &b,
+        )?;
+
+        z.push(zj);
+    }
+    z.reverse();
+
+    // round at n - mant_len, take the window of mant_len + 1 words
+    let pos = n - mant_len;
+    let mut r = rounding::round_at_impl(
+        params,
+        pub_keys,
+        &b,
+        pos,
+    )?;
+    // n.b., trivial zeros on top are omitted by addition
+    r.resize(n + 1, LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
+    let mut c = ParmCiphertext::triv(1, &pub_keys.encoder)?;
+    let mant_abs = if !(r[n].dimension == 0 && r[n].ciphertext.get_body().0 == 0) {
+        // W ∈ [2^(mant_len-1), 2^mant_len], c = [W = 2^mant_len] is the top canonical bit
+        let w = conversion::to_canonical_impl(
+            params,
+            pub_keys,
+            &r[pos..n+1].to_vec(),
+        )?;
+        c[0] = w[mant_len].clone();
+
+        // for c = 1, the lower bits are zero, the top one is set
+        let mut wm = w[..mant_len].to_vec();
+        wm[mant_len-1].add_uint_inplace(&c[0])?;
+        wm
+    } else {
+        r[pos..n].to_vec()
+    };
+
+    // apply the sign
+    let mant = selection::select_impl(
+        params,
+        pub_keys,
+        &neg,
+        &addition::opposite_impl(&mant_abs)?,
+        &mant_abs,
+    )?;
+
+    // E' = E + (c - Z) + (n - mant_len - pad)
+    let mut d = ParmCiphertext::triv(std::cmp::max(z.len(), 1), &pub_keys.encoder)?;
+    d[0] = c[0].clone();
+    for (dj, zj) in d.iter_mut().zip(z.iter()) {
+        dj.sub_uint_inplace(&zj[0])?;
+    }
+    let e_d = addition::sum_impl(
+        pub_keys,
+        &[e.clone(), d],
+    )?;
+    let e_dc = addition::add_const_impl(
+        params,
+        pub_keys,
+        &e_d,
+        (pos as i64) - (pad as i64),
+    )?;
+
+    // saturate into exp_len words
+    let exp = width::clamp_to_words_impl(
+        params,
+        pub_keys,
+        &e_dc,
+        exp_len,
+    )?;
+
+    Ok(ParmFloat {mant, exp})
+}
@@ -18,7 +18,7 @@ use concrete::LWE;
 use crate::params::Params;
 use crate::userovo::keys::PubKeySet;
 use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
-use super::{pbs,addition,signum};
+use super::{pbs,addition,signum,selection};
 
 /// Implementation of parallel maximum using signum
 pub fn max_impl(
@@ -27,77 +27,190 @@ pub fn max_impl(
     x: &ParmCiphertext,
     y: &ParmCiphertext,
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
-
-    let mut m: ParmCiphertext;
+    let m: ParmCiphertext;
 
     measure_duration!(
         ["Maximum ({}-bit)", x.len()],
         [
-            // r = x - y
-            //WISH after I implement manual bootstrap after addition, here it can be customized to powers of two (then first layer of bootstraps can be omitted in signum)
-            let r: ParmCiphertext = addition::add_sub_impl(
-                false,
+            let s_2: LWE = ge_s2(
+                params,
                 pub_keys,
                 x,
                 y,
             )?;
 
-            // s = 2 * sgn^+(r)
-            // returns one sample, not bootstrapped
-            let s_raw: ParmCiphertext = signum::sgn_recursion_raw(
-                params.bit_precision - 1,
+            m = selection::select_by_s2(
+                params,
                 pub_keys,
-                &r,
+                &s_2,
+                x,
+                y,
             )?;
-            //WISH copy this into vector (and test if this helps: concurrent memory access might be slow)
-            // bootstrap whether >= 0 (val =  2)
-            let s_2: LWE = pbs::f_0__pi_5__with_val(
+        ]
+    );
+
+    Ok(m)
+}
+
+/// Implementation of parallel minimum using signum
+pub fn min_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let m: ParmCiphertext;
+
+    measure_duration!(
+        ["Minimum ({}-bit)", x.len()],
+        [
+            let s_2: LWE = ge_s2(
+                params,
                 pub_keys,
-                &s_raw[0],
-                2,
+                x,
+                y,
             )?;
 
-            // align inputs
-            let mut xa = x.clone();
-            let mut ya = y.clone();
-            for _ in 0..((y.len() as i64) - (x.len() as i64)) {
-                xa.push(LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
-            }
-            for _ in 0..((x.len() as i64) - (y.len() as i64)) {
-                ya.push(LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
-            }
-
-            m = ParmCiphertext::triv(xa.len(), &pub_keys.encoder)?;
-
-            // calc x and y selectors
-            m.par_iter_mut().zip(xa.par_iter().zip(ya.par_iter())).for_each(| (mi, (xi, yi)) | {
-                // xi + 2s
-                let xi_p2s: LWE = xi.add_uint(&s_2).expect("add_uint failed.");
-                // yi - 2s
-                let yi_n2s: LWE = yi.sub_uint(&s_2).expect("sub_uint failed.");
-
-                // t, u (in parallel)
-                // init tmp variables in this scope, only references can be passed to threads
-                let mut ui = LWE::encrypt_uint_triv(0, &pub_keys.encoder).expect("LWE::encrypt_uint_triv failed.");
-                let uir = &mut ui;
-
-                // parallel pool: mi, ui
-                thread::scope(|miui_scope| {
-                    miui_scope.spawn(|_| {
-                        // mi = ReLU+(xi + 2s)
-                        *mi    = pbs::relu_plus__pi_5(pub_keys, &xi_p2s).expect("pbs::relu_plus__pi_5 failed.");   // ti
-                    });
-                    miui_scope.spawn(|_| {
-                        // ui = ReLU+(yi + 2s)
-                        *uir   = pbs::relu_plus__pi_5(pub_keys, &yi_n2s).expect("pbs::relu_plus__pi_5 failed.");
-                    });
-                }).expect("thread::scope miui_scope failed.");
-
-                // t + u
-                mi.add_uint_inplace(&ui).expect("add_uint_inplace failed.");
-            });
+            // select other way round
+            m = selection::select_by_s2(
+                params,
+                pub_keys,
+                &s_2,
+                y,
+                x,
+            )?;
         ]
     );
 
     Ok(m)
 }
+
+/// Implementation of absolute value using signum
+pub fn abs_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let a: ParmCiphertext;
+
+    measure_duration!(
+        ["Absolute value ({}-bit)", x.len()],
+        [
+            let s_2: LWE = ge_s2(
+                params,
+                pub_keys,
+                x,
+                &ParmCiphertext::empty(),
+            )?;
+
+            a = selection::select_by_s2(
+                params,
+                pub_keys,
+                &s_2,
+                x,
+                &addition::opposite_impl(x)?,
+            )?;
+        ]
+    );
+
+    Ok(a)
+}
+
+/// Implementation of argmax: returns encrypted index of the (first) maximum, and the maximum itself
+/// * pairwise tournament, where in case of a tie, the lower index wins
+pub fn argmax_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    a: &[ParmCiphertext],
+) -> Result<(ParmCiphertext, ParmCiphertext), Box<dyn Error>> {
+    if a.len() == 0 {
+        return Err("Argmax of an empty slice.".into());
+    }
+
+    // bit-length of index
+    let mut idx_len = 0usize;
+    while (1usize << idx_len) < a.len() {idx_len += 1;}
+
+    // pairs (index, value), indices are trivial samples
+    let mut iv: Vec<(ParmCiphertext, ParmCiphertext)> = Vec::new();
+    for (i, ai) in a.iter().enumerate() {
+        let mut ci = ParmCiphertext::empty();
+        for j in 0..idx_len {
+            ci.push(LWE::encrypt_uint_triv(((i >> j) & 1) as u32, &pub_keys.encoder)?);
+        }
+        iv.push((ci, ai.clone()));
+    }
+
+    measure_duration!(
+        ["Argmax ({} elements)", a.len()],
+        [
+            while iv.len() > 1 {
+                iv = iv.par_chunks(2).map(|ivc| {
+                    if ivc.len() == 1 {
+                        return ivc[0].clone();
+                    }
+                    let (il, vl) = &ivc[0];
+                    let (ir, vr) = &ivc[1];
+
+                    // left wins iff vl >= vr
+                    let s_2: LWE = ge_s2(params, pub_keys, vl, vr).expect("ge_s2 failed.");
+
+                    // select index & value (in parallel)
+                    let mut iw = ParmCiphertext::empty();
+                    let mut vw = ParmCiphertext::empty();
+                    let iwr = &mut iw;
+                    let vwr = &mut vw;
+                    thread::scope(|iv_scope| {
+                        iv_scope.spawn(|_| {
+                            *iwr = selection::select_by_s2(params, pub_keys, &s_2, il, ir).expect("selection::select_by_s2 failed.");
+                        });
+                        iv_scope.spawn(|_| {
+                            *vwr = selection::select_by_s2(params, pub_keys, &s_2, vl, vr).expect("selection::select_by_s2 failed.");
+                        });
+                    }).expect("thread::scope iv_scope failed.");
+
+                    (iw, vw)
+                }).collect();
+            }
+        ]
+    );
+
+    Ok(iv.swap_remove(0))
+}
+
+/// Bootstrapped `2` if `X ≥ Y`, `-2` otherwise
+fn ge_s2(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<LWE, Box<dyn Error>> {
+    // relu_plus does not exist for pi = 3
+    if params.bit_precision < 4 {
+        return Err(format!("Maximum, minimum & co. not implemented for pi = {}.", params.bit_precision).into());
+    }
+
+    // r = x - y
+    //WISH after I implement manual bootstrap after addition, here it can be customized to powers of two (then first layer of bootstraps can be omitted in signum)
+    let r: ParmCiphertext = addition::add_sub_impl(
+        false,
+        pub_keys,
+        x,
+        y,
+    )?;
+
+    // s = 2 * sgn^+(r)
+    // returns one sample, not bootstrapped
+    let s_raw: ParmCiphertext = signum::sgn_recursion_raw(
+        params.bit_precision - 1,
+        pub_keys,
+        &r,
+    )?;
+    //WISH copy this into vector (and test if this helps: concurrent memory access might be slow)
+    // bootstrap whether >= 0 (val =  2)
+    Ok(pbs::f_0__with_val(
+        pub_keys,
+        &s_raw[0],
+        2,
+    )?)
+}
@@ -3,9 +3,20 @@ pub mod pbs;
 pub mod addition;
 pub mod scalar_multiplication;
 pub mod signum;
+pub mod comparison;
 pub mod maximum;
+pub mod selection;
 pub mod multiplication;
 pub mod squaring;
+pub mod polynomial;
+pub mod division;
+pub mod square_root;
 pub mod rounding;
+pub mod shifting;
+pub mod conversion;
+pub mod bitwise;
+pub mod width;
+pub mod fixed_point;
+pub mod floating_point;
 
 pub mod neural_network;
@@ -25,6 +25,13 @@ use super::{pbs,addition};
 //  Multiplication
 //
 
+/// Shortest length, for which Karatsuba is used (shorter operands are multiplied by schoolbook)
+pub const MUL_KARATSUBA_MIN_LEN: usize = 14;
+/// Lengths above `MUL_KARATSUBA_MIN_LEN`, for which schoolbook is faster nevertheless
+pub const MUL_SCHOOLBOOK_EXTRA_LENS: [usize; 1] = [15];
+/// Ratio of lengths, from which the longer operand is split into chunks (instead of padding the shorter one)
+pub const MUL_CHUNKED_MIN_RATIO: usize = 2;
+
 /// Choose & call appropriate algorithm for a product of two ciphertexts (Karatsuba, or schoolbook multiplication)
 pub fn mul_impl(
     pub_keys: &PubKeySet,
@@ -33,6 +40,9 @@ pub fn mul_impl(
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
 
     //  Karatsuba for lengths 14 or >= 16, otherwise schoolbook (i.e., lengths < 14 or 15)
+    //  Karatsuba recurs into mul_impl, hence there is no limit on length
+    //  Unbalanced operands are not padded: the shorter one < 14 goes to rectangular schoolbook,
+    //  otherwise, if the longer one is at least twice as long, it is split into chunks
     //
     //  e.g., 32-bit:
     //                /  8
@@ -47,10 +57,45 @@ pub fn mul_impl(
     //          17  ---  9
     //                \ 10
 
+    // |X| ≥ 2 does not exist for pi = 3
+    if pub_keys.encoder.nb_bit_precision < 4 {
+        return Err(format!("Multiplication not implemented for pi = {}.", pub_keys.encoder.nb_bit_precision).into());
+    }
+
+    // x_l is the longer one
+    let (x_l, y_s) = if x.len() >= y.len() {(x, y)} else {(y, x)};
+
+    // resolve short & unbalanced operands (without padding)
+    if y_s.len() == 0 {
+        return Ok(ParmCiphertext::triv(1, &pub_keys.encoder)?);
+    }
+    if x_l.len() == 1 {
+        return mul_1word(
+            pub_keys,
+            x_l,
+            y_s,
+        );
+    }
+    if y_s.len() < MUL_KARATSUBA_MIN_LEN {
+        // rectangular schoolbook
+        return mul_schoolbook(
+            pub_keys,
+            x_l,
+            y_s,
+        );
+    }
+    if x_l.len() >= MUL_CHUNKED_MIN_RATIO * y_s.len() {
+        return mul_chunked(
+            pub_keys,
+            x_l,
+            y_s,
+        );
+    }
+
     let mut x_in = x.clone();
     let mut y_in = y.clone();
 
-    // align lengths of x & y
+    // align lengths of x & y (they differ less than MUL_CHUNKED_MIN_RATIO times)
     if x_in.len() != y_in.len() {
         //TODO check if this is efficient
 
@@ -66,23 +111,16 @@ pub fn mul_impl(
     }
 
     let p = match x_in.len() {
-        l if l == 0 => ParmCiphertext::triv(1, &pub_keys.encoder)?,
-        l if l == 1 => mul_1word(
-            pub_keys,
-            &x_in,
-            &y_in,
-        )?,
-        l if l < 14 || l == 15 => mul_schoolbook(
+        l if MUL_SCHOOLBOOK_EXTRA_LENS.contains(&l) => mul_schoolbook(
             pub_keys,
             &x_in,
             &y_in,
         )?,
-        l if l <= 32 => mul_karatsuba(
+        _ => mul_karatsuba(
             pub_keys,
             &x_in,
             &y_in,
         )?,
-        _ => return Err(format!("Multiplication for {}-word integers not implemented.", x_in.len()).into()),
     };
 
     Ok(p)
@@ -96,7 +134,7 @@ fn mul_karatsuba(
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
 
     //WISH  be able to calculate n and n-1 bit numbers (useful for squaring of non-power of two lengths)
-    //      in the end, it will be needed in schoolbook, too
+    //      n.b., unbalanced operands are resolved in mul_impl (chunks or rectangular schoolbook)
     assert_eq!(x.len(), y.len());
 
     // not needed: let len1 = x.len() / 2;
@@ -302,7 +340,45 @@ fn mul_karatsuba(
     Ok(res)
 }
 
-/// Schoolbook multiplication `O(n^2)`
+/// Multiplication of unbalanced operands: the longer one is split into chunks of the length of the shorter one
+/// * products of chunks are calculated in parallel, then shifted & added up
+fn mul_chunked(
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+
+    let chunk_len = y.len();
+    let x_chunks: Vec<ParmCiphertext> = x.chunks(chunk_len).map(|xc| xc.to_vec()).collect();
+
+    measure_duration!(
+        ["Multiplication chunked ({}-bit by {}-bit)", x.len(), y.len()],
+        [
+            // products of chunks
+            let mut prods = vec![ParmCiphertext::empty(); x_chunks.len()];
+            prods.par_iter_mut().zip(x_chunks.par_iter()).for_each(| (pk, xk) | {
+                *pk = mul_impl(pub_keys, xk, y).expect("mul_impl failed.");
+            });
+
+            // shift & add up
+            let mut prods_sh: Vec<ParmCiphertext> = Vec::new();
+            for (k, pk) in prods.iter().enumerate() {
+                let mut pk_sh = ParmCiphertext::triv(k * chunk_len, &pub_keys.encoder)?;
+                pk_sh.extend(pk.iter().cloned());
+                prods_sh.push(pk_sh);
+            }
+            let res = addition::sum_impl(
+                pub_keys,
+                &prods_sh,
+            )?;
+        ]
+    );
+
+    Ok(res)
+}
+
+/// Schoolbook multiplication `O(n·m)`
+/// * operands may differ in length
 fn mul_schoolbook(
     pub_keys: &PubKeySet,
     x: &ParmCiphertext,
@@ -310,7 +386,7 @@ fn mul_schoolbook(
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
 
     measure_duration!(
-        ["Multiplication schoolbook ({}-bit)", x.len()],
+        ["Multiplication schoolbook ({}-bit by {}-bit)", x.len(), y.len()],
         [
             // calc multiplication array
             let mulary = fill_mulary(
@@ -320,29 +396,14 @@ fn mul_schoolbook(
             )?;
 
             // reduce multiplication array
-            //TODO write a function that will be common with scalar_multiplication (if this is possible with strategies 2+)
-            let mut intmd = vec![ParmCiphertext::empty(); 2];
-            let mut idx = 0usize;
-            intmd[idx] = addition::add_sub_noise_refresh(
-                true,
+            let p = addition::sum_impl(
                 pub_keys,
-                &mulary[0],
-                &mulary[1],
+                &mulary,
             )?;
-
-            for i in 2..x.len() {
-                idx ^= 1;
-                intmd[idx] = addition::add_sub_noise_refresh(
-                    true,
-                    pub_keys,
-                    &intmd[idx ^ 1],
-                    &mulary[i],
-                )?;
-            }
         ]
     );
 
-    Ok(intmd[idx].clone())
+    Ok(p)
 }
 
 /// Product of two 1-word ciphertexts
@@ -368,20 +429,19 @@ fn mul_1word(
 }
 
 /// Fill multiplication array (for schoolbook multiplication)
+/// * one row per word of `y`
 fn fill_mulary(
     pub_keys: &PubKeySet,
     x: &ParmCiphertext,
     y: &ParmCiphertext,
 ) -> Result<Vec<ParmCiphertext>, Box<dyn Error>> {
 
-    assert_eq!(x.len(), y.len());
-
     let len = x.len();
 
     // fill multiplication array
     //TODO check the size, it might grow outsite due to redundant representation
     //TODO try different approaches and compare
-    let mut mulary = vec![ParmCiphertext::triv(2*len, &pub_keys.encoder)?; len];
+    let mut mulary = vec![ParmCiphertext::triv(len + y.len(), &pub_keys.encoder)?; y.len()];
 
     // nested parallel iterators work as expected: they indeed create nested pools
     mulary.par_iter_mut().zip(y.par_iter().enumerate()).for_each(| (x_yj, (j, yj)) | {
@@ -440,11 +500,11 @@ pub fn mul_lwe(
             thread::scope(|pn_scope| {
                 pn_scope.spawn(|_| {
                     // pos = ...
-                    *posr  = pbs::a_2__pi_5(pub_keys, &pxpy).expect("pbs::a_2__pi_5 failed.");
+                    *posr  = pbs::a_2(pub_keys, &pxpy).expect("pbs::a_2 failed.");
                 });
                 pn_scope.spawn(|_| {
                     // neg = ...
-                    *negr  = pbs::a_2__pi_5(pub_keys, &pxny).expect("pbs::a_2__pi_5 failed.");
+                    *negr  = pbs::a_2(pub_keys, &pxny).expect("pbs::a_2 failed.");
                 });
             }).expect("thread::scope pn_scope failed.");
 
@@ -117,6 +117,18 @@ impl NeuralNetwork {
         ol
     }
 
+    /// Evaluate Neural Network & reduce its output layer into a label (index of the first maximum)
+    pub fn eval_label<T: Clone + ParmArithmetics>(
+        &self,
+        pc: &ParmesanCloudovo,
+        inputs: &Vec<T>,
+    ) -> T {
+        let out = self.eval::<T>(pc, inputs);
+
+        let (label, _max) = ParmArithmetics::argmax(pc, &out);
+        label
+    }
+
     /// Evaluate a layer of NN
     pub fn eval_layer<T: Clone + ParmArithmetics>(
         &self,
@@ -129,22 +141,40 @@ impl NeuralNetwork {
         //DBG
         println!("\n>   LAYER   ====================================================================");
 
+        // apply weights: each input is multiplied by all weights of the layer at once (sharing common subexpressions)
+        //  n.b., weights of perceptron p end up in wa[p]
+        let mut wa: Vec<Vec<T>> = vec![Vec::new(); layer.len()];
+        measure_duration!(
+            ["Layer weights application ({} inputs)", input.len()],
+            [
+                for (ii, ai) in input.iter().enumerate() {
+                    let ks: Vec<i64> = layer.iter().filter(|perc| ii < perc.w.len()).map(|perc| perc.w[ii] as i64).collect();
+                    let mut kas = ParmArithmetics::scalar_mul_many(pc, ai, &ks).into_iter();
+                    for (wap, perc) in wa.iter_mut().zip(layer.iter()) {
+                        if ii < perc.w.len() {
+                            wap.push(kas.next().expect("scalar_mul_many returned too few values."));
+                        }
+                    }
+                }
+            ]
+        );
+
         // evaluate perceptron by type
-        for (_ip, perc) in layer.iter().enumerate() {
+        for (_ip, (perc, wap)) in layer.iter().zip(wa.iter()).enumerate() {
             measure_duration!(
                 ["{}. perceptron evaluation", _ip],
                 [
                     match &perc.t {
                         PercType::MAX => {
-                            let max = self.max_pool::<T>(pc, &perc.w, input, perc.b);
+                            let max = self.max_pool_weighted::<T>(pc, wap, perc.b);
                             output.push(max);
                         },
                         PercType::LIN => {
-                            let aff = self.affine_pool::<T>(pc, &perc.w, input, perc.b);
+                            let aff = self.affine_pool_weighted::<T>(pc, wap, perc.b);
                             output.push(aff);
                         },
                         PercType::ACT => {
-                            let aff = self.affine_pool::<T>(pc, &perc.w, input, perc.b);
+                            let aff = self.affine_pool_weighted::<T>(pc, wap, perc.b);
                             //DBG
                             println!("\n>   ReLU   ---------------------------------------------------------------------");
                             output.push(self.act_fn::<T>(pc, &aff));
@@ -155,6 +185,7 @@ impl NeuralNetwork {
         }
     }
 
+    /// Sum of inputs `a` weighted by `w` (i.e., a dot product) + bias
     pub fn affine_pool<T: Clone + ParmArithmetics>(
         &self,
         pc: &ParmesanCloudovo,
@@ -162,23 +193,45 @@ impl NeuralNetwork {
         a: &Vec<T>,
         b: i64,
     ) -> T {
+        let wa = self.apply_weights::<T>(pc, w, a);
+        self.affine_pool_weighted::<T>(pc, &wa, b)
+    }
+
+    /// Maximum of inputs `a` weighted by `w` + bias
+    pub fn max_pool<T: Clone + ParmArithmetics>(
+        &self,
+        pc: &ParmesanCloudovo,
+        w: &Vec<i32>,
+        a: &Vec<T>,
+        b: i64,
+    ) -> T {
+        let wa = self.apply_weights::<T>(pc, w, a);
+        self.max_pool_weighted::<T>(pc, &wa, b)
+    }
+
+    fn apply_weights<T: Clone + ParmArithmetics>(
+        &self,
+        pc: &ParmesanCloudovo,
+        w: &Vec<i32>,
+        a: &Vec<T>,
+    ) -> Vec<T> {
+        w.iter().zip(a.iter()).map(|(wi, ai)| ParmArithmetics::scalar_mul(pc, *wi as i64, ai)).collect()
+    }
+
+    /// Sum of weighted inputs `wa` (i.e., a dot product) + bias
+    fn affine_pool_weighted<T: Clone + ParmArithmetics>(
+        &self,
+        pc: &ParmesanCloudovo,
+        wa: &Vec<T>,
+        b: i64,
+    ) -> T {
 
-        let mut res: T = ParmArithmetics::zero();
-        let mut agg: T;
-        let mut scm: T;
         //DBG
         println!("\n>   AFFINE POOL   --------------------------------------------------------------");
 
         // dot product
-        for (wi, ai) in w.iter().zip(a.iter()) {
-            //DBG
-            println!(">>> scalar mul: {} ...", *wi);
-            scm = ParmArithmetics::scalar_mul(pc, *wi, ai);
-            println!(">>> addition ...");
-            agg = ParmArithmetics::add(pc, &res, &scm);
-            //TODO try directly to res, or implement add_inplace? (rather not..)
-            res = agg.clone();
-        }
+        println!(">>> sum ...");
+        let res: T = ParmArithmetics::sum(pc, wa);
 
         //DBG
         println!(">>> add bias {} ...", b);
@@ -186,23 +239,16 @@ impl NeuralNetwork {
         ParmArithmetics::add_const(pc, &res, b)
     }
 
-    pub fn max_pool<T: Clone + ParmArithmetics>(
+    /// Maximum of weighted inputs `wa` + bias
+    fn max_pool_weighted<T: Clone + ParmArithmetics>(
         &self,
         pc: &ParmesanCloudovo,
-        w: &Vec<i32>,
-        a: &Vec<T>,
+        wa: &Vec<T>,
         b: i64,
     ) -> T {
 
-        let mut wa: Vec<T> = Vec::new();
-
-        // apply weights
-        for (wi, ai) in w.iter().zip(a.iter()) {
-            wa.push(ParmArithmetics::scalar_mul(pc, *wi, ai));
-        }
-
         // locate maximum
-        let res = self.max_pool_recursion::<T>(pc, &wa);
+        let res = self.max_pool_recursion::<T>(pc, wa);
 
         // + bias
         ParmArithmetics::add_const(pc, &res, b)
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::ops::RangeInclusive;
 
 #[allow(unused_imports)]
 use colored::Colorize;
@@ -7,22 +8,195 @@ use concrete::LWE;
 
 use crate::userovo::keys::PubKeySet;
 
+
+// =============================================================================
 //
-//  X (positive half)
+//  Look-Up Table
 //
-pub fn pos_id(
+
+/// # Look-Up Table for programmable bootstrapping
+/// * holds values for `X` in `[0, 2^(pi-1))`, the rest follows from negacyclicity:
+///   `f(X - 2^(pi-1)) = -f(X)`
+/// * values are stored mod `2^pi`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut {
+    pi: usize,
+    vals: Vec<f64>,
+}
+
+impl Lut {
+
+    /// Create LUT from function `f` over signed plaintext domain `dom` (subset of `[-2^(pi-1), 2^(pi-1))`)
+    /// * values outside `dom` are don't-care (set to zero, unless implied by negacyclicity)
+    /// * fails if `f` is not negacyclic on `dom`, i.e., if `f(X - 2^(pi-1)) != -f(X)` for some `X`, `X - 2^(pi-1)` in `dom`
+    ///
+    /// E.g., signum for `pi = 5`:
+    /// ```text
+    /// let sgn = Lut::new(5, -15..=15, |x| x.signum())?;
+    /// let s = pbs::eval_lut(pub_keys, &c, &sgn)?;
+    /// ```
+    pub fn new<F: Fn(i32) -> i32>(
+        pi: usize,
+        dom: RangeInclusive<i32>,
+        f: F,
+    ) -> Result<Lut, Box<dyn Error>> {
+        if pi < 2 || pi > 16 {
+            return Err(format!("LUT bit precision pi = {} out of range.", pi).into());
+        }
+        let half = 1i32 << (pi - 1);
+        if *dom.start() < -half || *dom.end() >= half {
+            return Err(format!("LUT domain {:?} exceeds [{}, {}) for pi = {}.", dom, -half, half, pi).into());
+        }
+
+        let mut vals = Vec::new();
+        for x in 0..half {
+            // X and X - half share the same LUT entry
+            let pos = if dom.contains(&x)          { Some( f(x))        } else { None };
+            let neg = if dom.contains(&(x - half)) { Some(-f(x - half)) } else { None };
+
+            let v = match (pos, neg) {
+                (Some(p), Some(n)) => {
+                    if (p - n).rem_euclid(1 << pi) != 0 {
+                        return Err(format!("LUT is not negacyclic: f({}) = {}, but f({}) = {}.", x, p, x - half, -n).into());
+                    }
+                    p
+                },
+                (Some(p), None) => p,
+                (None, Some(n)) => n,
+                (None, None)    => 0,
+            };
+
+            vals.push(v.rem_euclid(1 << pi) as f64);
+        }
+
+        Ok(Lut {
+            pi,
+            vals,
+        })
+    }
+
+    /// Get bit precision of the LUT
+    pub fn pi(&self) -> usize {
+        self.pi
+    }
+
+    /// Evaluate LUT on a plaintext `m` in `[0, 2^pi)`
+    pub fn eval_plain(&self, m: u32) -> Result<u32, Box<dyn Error>> {
+        let half = self.vals.len();
+        let m = m as usize;
+        let fm = if m < half { self.vals[m] as i64 }
+            else if m < 2 * half { -(self.vals[m - half] as i64) }
+            else { return Err(format!("Word m = {} does not fit {}-bit LUT.", m, self.pi).into()) };
+        Ok(fm.rem_euclid(1 << self.pi) as u32)
+    }
+}
+
+/// Evaluate LUT via programmable bootstrapping (followed by key switching)
+/// * LUT must match the bit precision of the encoder
+/// * trivial samples are evaluated without bootstrapping
+pub fn eval_lut(
     pub_keys: &PubKeySet,
     c: &LWE,
+    lut: &Lut,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
+    if lut.pi != pub_keys.encoder.nb_bit_precision {
+        return Err(format!("{}-bit LUT does not fit bit precision {}.", lut.pi, pub_keys.encoder.nb_bit_precision).into());
+    }
+
+    // resolve trivial case
     if c.dimension == 0 {
-        return Ok(c.clone());
+        let fm = lut.eval_plain(c.decrypt_uint_triv()?)?;
+        Ok(LWE::encrypt_uint_triv(fm, &pub_keys.encoder)?)
+    } else {
+        Ok(c.bootstrap_with_function(pub_keys.bsk, |x| lut.vals[x as usize], pub_keys.encoder)?
+            .keyswitch(pub_keys.ksk)?)
     }
+}
+
+
+// =============================================================================
+//
+//  Common LUTs (for any pi)
+//
+
+//
+//  X ⋛ ±k
+//
+fn lut_f_k(
+    pi: usize,
+    k: i32,
+) -> Result<Lut, Box<dyn Error>> {
+    let d = (1 << (pi - 1)) - k;
+    Lut::new(pi, -d..=d, |x| if x >= k {1} else if x <= -k {-1} else {0})
+}
+
+//
+//  X ⋛ ±1 (× val)
+//
+#[allow(non_snake_case)]
+fn lut_f_1__with_val(
+    pi: usize,
+    val: u32,
+) -> Result<Lut, Box<dyn Error>> {
+    let d = (1 << (pi - 1)) - 1;
+    Lut::new(pi, -d..=d, |x| x.signum() * val as i32)
+}
 
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| x, pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
+//
+//  X ≥ 0 /sgn+/ (× val)
+//
+#[allow(non_snake_case)]
+fn lut_f_0__with_val(
+    pi: usize,
+    val: u32,
+) -> Result<Lut, Box<dyn Error>> {
+    let half = 1 << (pi - 1);
+    Lut::new(pi, -half..=(half - 1), |x| if x >= 0 {val as i32} else {-(val as i32)})
+}
 
-    Ok(res)
+//
+//  |X| ≥ k   (around zero)
+//
+fn lut_a_k(
+    pi: usize,
+    k: i32,
+) -> Result<Lut, Box<dyn Error>> {
+    let quarter = 1 << (pi - 2);
+    Lut::new(pi, -quarter..=(quarter - 1), |x| if x.abs() >= k {1} else {0})
+}
+
+//
+//  ReLU+ (cf. relu_plus__pi_5)
+//
+fn lut_relu_plus(
+    pi: usize,
+) -> Result<Lut, Box<dyn Error>> {
+    Lut::new(pi, -((1 << (pi - 1)) - 4)..=3, |x| if x > 0 {x - 2} else {0})
+}
+
+//
+//  Rounding PBS for 2y + s (cf. round_2y_s__pi_5)
+//
+fn lut_round_2y_s(
+    pi: usize,
+) -> Result<Lut, Box<dyn Error>> {
+    let half = 1 << (pi - 1);
+    Lut::new(pi, -(half - 2)..=(half - 3), |x| if x >= 2 {1} else if x <= -3 {-1} else {0})
+}
+
+//
+//  X (positive half)
+//
+pub fn pos_id(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    let pi = pub_keys.encoder.nb_bit_precision;
+    eval_lut(
+        pub_keys,
+        c,
+        &Lut::new(pi, 0..=((1 << (pi - 1)) - 1), |x| x)?
+    )
 }
 
 
@@ -39,11 +213,7 @@ pub fn id__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,1.,2.,3.,4.,5.,6.,7.,8.,7.,6.,5.,4.,3.,2.,1.]
-    )
+    eval_lut(pub_keys, c, &Lut::new(5, -8..=8, |x| x)?)
 }
 
 //
@@ -54,11 +224,7 @@ pub fn f_3__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,0.,0.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,0.,0.]
-    )
+    eval_lut(pub_keys, c, &lut_f_k(5, 3)?)
 }
 
 //
@@ -69,11 +235,7 @@ pub fn f_4__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,0.,0.,0.,1.,1.,1.,1.,1.,1.,1.,1.,1.,0.,0.,0.]
-    )
+    eval_lut(pub_keys, c, &lut_f_k(5, 4)?)
 }
 
 //
@@ -84,11 +246,7 @@ pub fn f_5__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,0.,0.,0.,0.,1.,1.,1.,1.,1.,1.,1.,0.,0.,0.,0.]
-    )
+    eval_lut(pub_keys, c, &lut_f_k(5, 5)?)
 }
 
 //
@@ -100,12 +258,7 @@ pub fn g_2__pi_5__with_val(
     c: &LWE,
     val: u32,
 ) -> Result<LWE, Box<dyn Error>> {
-    let vf = val as f64;
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,0.,vf,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,vf,0.,]
-    )
+    eval_lut(pub_keys, c, &Lut::new(5, -13..=13, |x| if x.abs() == 2 {x.signum() * val as i32} else {0})?)
 }
 
 //
@@ -117,12 +270,7 @@ pub fn f_1__pi_5__with_val(
     c: &LWE,
     val: u32,
 ) -> Result<LWE, Box<dyn Error>> {
-    let vf = val as f64;
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf]
-    )
+    eval_lut(pub_keys, c, &lut_f_1__with_val(5, val)?)
 }
 
 //
@@ -134,12 +282,7 @@ pub fn f_0__pi_5__with_val(
     c: &LWE,
     val: u32,
 ) -> Result<LWE, Box<dyn Error>> {
-    let vf = val as f64;
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf,vf]
-    )
+    eval_lut(pub_keys, c, &lut_f_0__with_val(5, val)?)
 }
 
 //
@@ -150,11 +293,7 @@ pub fn a_2__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,0.,1.,1.,1.,1.,1.,1.,31.,31.,31.,31.,31.,31.,31.,0.]
-    )
+    eval_lut(pub_keys, c, &lut_a_k(5, 2)?)
 }
 
 //
@@ -165,11 +304,7 @@ pub fn a_1__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,1.,1.,1.,1.,1.,1.,1.,31.,31.,31.,31.,31.,31.,31.,31.]
-    )
+    eval_lut(pub_keys, c, &lut_a_k(5, 1)?)
 }
 
 //
@@ -183,11 +318,7 @@ pub fn relu_plus__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,31.,0.,1.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.]
-    )
+    eval_lut(pub_keys, c, &lut_relu_plus(5)?)
 }
 
 //
@@ -202,21 +333,20 @@ pub fn round_2y_s__pi_5(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    eval_LUT_5(
-        pub_keys,
-        c,
-        [0.,0.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,0.,0.]
-    )
+    eval_lut(pub_keys, c, &lut_round_2y_s(5)?)
 }
 
 
 // =============================================================================
 //
-//  Logical (represented with pi = 3)
+//  Logical (bits in {0,1}, any pi)
+//
+//  Inputs are summed up with small weights, hence the argument stays within [0, 2^(pi-1)) or (-2^(pi-1), 2^(pi-1)),
+//  where any function fits the negacyclic LUT (i.e., there are no corner values).
 //
 
 //
-//  XOR
+//  X ⊕ Y
 //
 #[allow(non_snake_case)]
 pub fn XOR(
@@ -224,19 +354,13 @@ pub fn XOR(
     x: &LWE,
     y: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    // t = 2x + 2y
-    let mut t = x.mul_uint_constant(2)?;
-    t.add_uint_inplace(y)?; t.add_uint_inplace(y)?;
-    // bootstrap
-    //FIXME resolve corner values: 1 1 1/-1 -1 .. shift by 1/16 .. pi = 4, change encoding
-    let res = t.bootstrap_with_function(pub_keys.bsk, |x| [1.,1.,1.,7.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    // t = x + y
+    let t = x.add_uint(y)?;
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, 0..=2, |t| [0,1,0][t as usize])?)
 }
 
 //
-//  AND
+//  X ∧ Y
 //
 #[allow(non_snake_case)]
 pub fn AND(
@@ -246,16 +370,53 @@ pub fn AND(
 ) -> Result<LWE, Box<dyn Error>> {
     // t = x + y
     let t = x.add_uint(y)?;
-    // bootstrap
-    //FIXME resolve corner values: -1 -1/1 1 1 .. shift by 1/16 .. pi = 4, change encoding
-    let res = t.bootstrap_with_function(pub_keys.bsk, |x| [7.,7.,1.,1.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, 0..=2, |t| [0,0,1][t as usize])?)
+}
+
+//
+//  X ∨ Y
+//
+#[allow(non_snake_case)]
+pub fn OR(
+    pub_keys: &PubKeySet,
+    x: &LWE,
+    y: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    // t = x + y
+    let t = x.add_uint(y)?;
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, 0..=2, |t| [0,1,1][t as usize])?)
+}
+
+//
+//  ¬(X ∧ Y)
+//
+#[allow(non_snake_case)]
+pub fn NAND(
+    pub_keys: &PubKeySet,
+    x: &LWE,
+    y: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    // t = x + y
+    let t = x.add_uint(y)?;
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, 0..=2, |t| [1,1,0][t as usize])?)
+}
 
-    Ok(res)
+//
+//  X ∧ ¬Y
+//
+#[allow(non_snake_case)]
+pub fn AND_NOT(
+    pub_keys: &PubKeySet,
+    x: &LWE,
+    y: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    // t = x - y
+    let t = x.sub_uint(y)?;
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, -1..=1, |t| if t == 1 {1} else {0})?)
 }
 
 //
-//  XOR3
+//  X ⊕ Y ⊕ Z
 //
 #[allow(non_snake_case)]
 pub fn XOR_THREE(
@@ -264,20 +425,14 @@ pub fn XOR_THREE(
     y: &LWE,
     z: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    // t = 2(x + y + z)
-    let mut t = x.mul_uint_constant(2)?;
-    t.add_uint_inplace(y)?; t.add_uint_inplace(y)?;
-    t.add_uint_inplace(z)?; t.add_uint_inplace(z)?;
-    // bootstrap
-    //FIXME resolve corner values: 1/-1 -1 -1 -1 .. shift by 1/16 .. pi = 4, change encoding
-    let res = t.bootstrap_with_function(pub_keys.bsk, |x| [7.,7.,7.,7.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    // t = x + y + z
+    let mut t = x.add_uint(y)?;
+    t.add_uint_inplace(z)?;
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, 0..=3, |t| t & 1)?)
 }
 
 //
-//  2OF3
+//  2OF3 (majority)
 //
 #[allow(non_snake_case)]
 pub fn TWO_OF_THREE(
@@ -289,12 +444,7 @@ pub fn TWO_OF_THREE(
     // t = x + y + z
     let mut t = x.add_uint(y)?;
     t.add_uint_inplace(z)?;
-    // bootstrap
-    //FIXME resolve corner values: 1/-1 -1 -1 -1 .. shift by 1/16 .. pi = 4, change encoding
-    let res = t.bootstrap_with_function(pub_keys.bsk, |x| [1.,1.,1.,1.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, &t, &Lut::new(pub_keys.encoder.nb_bit_precision, 0..=3, |t| if t >= 2 {1} else {0})?)
 }
 
 
@@ -311,15 +461,7 @@ pub fn c_4__pi_2x4(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [15.,15.,15.,15.,15.,15.,15.,15.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &Lut::new(4, 0..=7, |_| -1)?)
 }
 
 
@@ -336,15 +478,7 @@ pub fn id__pi_3(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,1.,2.,1.][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &Lut::new(3, -2..=2, |x| x)?)
 }
 
 //
@@ -355,15 +489,7 @@ pub fn f_1__pi_3(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,1.,1.,1.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &lut_f_k(3, 1)?)
 }
 
 //
@@ -374,15 +500,7 @@ pub fn f_2__pi_3(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,0.,1.,0.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &lut_f_k(3, 2)?)
 }
 
 //
@@ -393,15 +511,7 @@ pub fn g_1__pi_3(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,1.,0.,1.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &Lut::new(3, -3..=3, |x| if x % 2 != 0 {x.signum()} else {0})?)
 }
 
 //
@@ -416,6 +526,30 @@ pub fn g_2__pi_3(
     f_2__pi_3(pub_keys, c)
 }
 
+//
+//  X ⋛ ±1 (× val)
+//
+#[allow(non_snake_case)]
+pub fn f_1__pi_3__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_1__with_val(3, val)?)
+}
+
+//
+//  X ≥ 0 /sgn+/ (× val)
+//
+#[allow(non_snake_case)]
+pub fn f_0__pi_3__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_0__with_val(3, val)?)
+}
+
 
 // =============================================================================
 //
@@ -430,15 +564,7 @@ pub fn id__pi_4(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,1.,2.,3.,4.,3.,2.,1.][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &Lut::new(4, -4..=4, |x| x)?)
 }
 
 //
@@ -449,15 +575,7 @@ pub fn f_2__pi_4(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,0.,1.,1.,1.,1.,1.,0.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &lut_f_k(4, 2)?)
 }
 
 //
@@ -468,15 +586,7 @@ pub fn f_3__pi_4(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,0.,0.,1.,1.,1.,0.,0.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &lut_f_k(4, 3)?)
 }
 
 //
@@ -487,15 +597,7 @@ pub fn g_2__pi_4(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,0.,1.,0.,0.,0.,1.,0.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
-
-    Ok(res)
+    eval_lut(pub_keys, c, &Lut::new(4, -7..=7, |x| if x.rem_euclid(4) == 2 {x.signum()} else {0})?)
 }
 
 //
@@ -507,17 +609,75 @@ pub fn g_1__pi_4__with_val(
     c: &LWE,
     val: u32,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
+    eval_lut(pub_keys, c, &Lut::new(4, -7..=7, |x| if x.abs() == 1 || x.abs() == 7 {x.signum() * val as i32} else {0})?)
+}
+
+//
+//  X ⋛ ±1 (× val)
+//
+#[allow(non_snake_case)]
+pub fn f_1__pi_4__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_1__with_val(4, val)?)
+}
 
-    let vf = val as f64;
+//
+//  X ≥ 0 /sgn+/ (× val)
+//
+#[allow(non_snake_case)]
+pub fn f_0__pi_4__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_0__with_val(4, val)?)
+}
 
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,vf,0.,0.,0.,0.,0.,vf][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
+//
+//  |X| ≥ 2
+//
+#[allow(non_snake_case)]
+pub fn a_2__pi_4(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_a_k(4, 2)?)
+}
 
-    Ok(res)
+//
+//  |X| ≥ 1   (i.e., squaring in {-1,0,1})
+//
+#[allow(non_snake_case)]
+pub fn a_1__pi_4(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_a_k(4, 1)?)
+}
+
+//
+//  ReLU+ (cf. relu_plus__pi_5)
+//
+#[allow(non_snake_case)]
+pub fn relu_plus__pi_4(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_relu_plus(4)?)
+}
+
+//
+//  Rounding PBS for 2y + s (cf. round_2y_s__pi_5)
+//
+#[allow(non_snake_case)]
+pub fn round_2y_s__pi_4(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_round_2y_s(4)?)
 }
 
 
@@ -534,15 +694,18 @@ pub fn id__pi_7(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
-
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,1.,2.,3.,4.,5.,6.,7.,8.,9.,10.,11.,12.,13.,14.,15.,16.,17.,18.,19.,20.,21.,22.,23.,24.,25.,26.,27.,28.,29.,30.,31.,32.,31.,30.,29.,28.,27.,26.,25.,24.,23.,22.,21.,20.,19.,18.,17.,16.,15.,14.,13.,12.,11.,10.,9.,8.,7.,6.,5.,4.,3.,2.,1.][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
+    eval_lut(pub_keys, c, &Lut::new(7, -32..=32, |x| x)?)
+}
 
-    Ok(res)
+//
+//  X ⋛ ±4
+//
+#[allow(non_snake_case)]
+pub fn f_4__pi_7(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_k(7, 4)?)
 }
 
 //
@@ -553,82 +716,211 @@ pub fn f_14__pi_7(
     pub_keys: &PubKeySet,
     c: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    //TODO resolve trivial case
-    if c.dimension == 0 {
-        return Ok(c.clone());
-    }
+    eval_lut(pub_keys, c, &lut_f_k(7, 14)?)
+}
 
-    let res = c.bootstrap_with_function(pub_keys.bsk, |x| [0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,1.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,0.,][x as usize], pub_keys.encoder)?
-               .keyswitch(pub_keys.ksk)?;
+//
+//  X ⋛ ±1 (× val)
+//
+#[allow(non_snake_case)]
+pub fn f_1__pi_7__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_1__with_val(7, val)?)
+}
 
-    Ok(res)
+//
+//  X ≥ 0 /sgn+/ (× val)
+//
+#[allow(non_snake_case)]
+pub fn f_0__pi_7__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_f_0__with_val(7, val)?)
 }
 
+//
+//  |X| ≥ 2
+//
+#[allow(non_snake_case)]
+pub fn a_2__pi_7(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_a_k(7, 2)?)
+}
+
+//
+//  |X| ≥ 1   (i.e., squaring in {-1,0,1})
+//
+#[allow(non_snake_case)]
+pub fn a_1__pi_7(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_a_k(7, 1)?)
+}
+
+//
+//  ReLU+ (cf. relu_plus__pi_5)
+//
+#[allow(non_snake_case)]
+pub fn relu_plus__pi_7(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_relu_plus(7)?)
+}
+
+//
+//  Rounding PBS for 2y + s (cf. round_2y_s__pi_5)
+//
+#[allow(non_snake_case)]
+pub fn round_2y_s__pi_7(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    eval_lut(pub_keys, c, &lut_round_2y_s(7)?)
+}
 
 // =============================================================================
 //
-//  Eval LUT
+//  Dispatch by Bit Precision
+//
+//  Pick the variant of a function that corresponds with the bit precision
+//  of the encoder (i.e., pi of the params that the keys were generated for)
+//
+
+//
+//  X (around zero)
+//
+pub fn id(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        3 => id__pi_3(pub_keys, c),
+        4 => id__pi_4(pub_keys, c),
+        5 => id__pi_5(pub_keys, c),
+        7 => id__pi_7(pub_keys, c),
+        pi => Err(format!("PBS id not implemented for pi = {}.", pi).into()),
+    }
+}
+
+//
+//  X ⋛ ±4
+//
+pub fn f_4(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        5 => f_4__pi_5(pub_keys, c),
+        7 => f_4__pi_7(pub_keys, c),
+        pi => Err(format!("PBS f_4 not implemented for pi = {}.", pi).into()),
+    }
+}
+
+//
+//  X ⋛ ±1 (× val)
 //
+#[allow(non_snake_case)]
+pub fn f_1__with_val(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+    val: u32,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        3 => f_1__pi_3__with_val(pub_keys, c, val),
+        4 => f_1__pi_4__with_val(pub_keys, c, val),
+        5 => f_1__pi_5__with_val(pub_keys, c, val),
+        7 => f_1__pi_7__with_val(pub_keys, c, val),
+        pi => Err(format!("PBS f_1 not implemented for pi = {}.", pi).into()),
+    }
+}
 
+//
+//  X ≥ 0 /sgn+/ (× val)
+//
 #[allow(non_snake_case)]
-fn eval_LUT_5(
+pub fn f_0__with_val(
     pub_keys: &PubKeySet,
     c: &LWE,
-    lut: [f64; 1 << (5-1)],
+    val: u32,
 ) -> Result<LWE, Box<dyn Error>> {
-    // resolve trivial case
-    if c.dimension == 0 {
-        let  m = c.decrypt_uint_triv()?;
-        let fm = if m < (1 << (5-1)) { lut[m as usize] }
-            else if m < (1 << 5) { -lut[(m as i32 - (1 << (5-1))) as usize] }
-            else {panic!("Word m = {} does not fit 5-bit LUT.", m)};
-        let fm_u = ((fm as i32) & ((1 << 5) - 1)) as u32;
-        Ok(LWE::encrypt_uint_triv(fm_u, &pub_keys.encoder)?)
-    } else {
-        Ok(c.bootstrap_with_function(pub_keys.bsk, |x| lut[x as usize], pub_keys.encoder)?
-            .keyswitch(pub_keys.ksk)?)
+    match pub_keys.encoder.nb_bit_precision {
+        3 => f_0__pi_3__with_val(pub_keys, c, val),
+        4 => f_0__pi_4__with_val(pub_keys, c, val),
+        5 => f_0__pi_5__with_val(pub_keys, c, val),
+        7 => f_0__pi_7__with_val(pub_keys, c, val),
+        pi => Err(format!("PBS f_0 not implemented for pi = {}.", pi).into()),
     }
 }
 
+//
+//  |X| ≥ 2
+//  n.b., not possible for pi = 3 (X = 2 and X = -2 must map to the same value)
+//
+pub fn a_2(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        4 => a_2__pi_4(pub_keys, c),
+        5 => a_2__pi_5(pub_keys, c),
+        7 => a_2__pi_7(pub_keys, c),
+        pi => Err(format!("PBS a_2 not implemented for pi = {}.", pi).into()),
+    }
+}
 
+//
+//  |X| ≥ 1   (i.e., squaring in {-1,0,1})
+//
+pub fn a_1(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        4 => a_1__pi_4(pub_keys, c),
+        5 => a_1__pi_5(pub_keys, c),
+        7 => a_1__pi_7(pub_keys, c),
+        pi => Err(format!("PBS a_1 not implemented for pi = {}.", pi).into()),
+    }
+}
+
+//
+//  ReLU+ (cf. relu_plus__pi_5)
+//  n.b., not possible for pi = 3 (X = 1 and X = -3 must map to opposite values)
+//
+pub fn relu_plus(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        4 => relu_plus__pi_4(pub_keys, c),
+        5 => relu_plus__pi_5(pub_keys, c),
+        7 => relu_plus__pi_7(pub_keys, c),
+        pi => Err(format!("PBS relu_plus not implemented for pi = {}.", pi).into()),
+    }
+}
+
+//
+//  Rounding PBS for 2y + s (cf. round_2y_s__pi_5)
+//  n.b., not possible for pi = 3, cf. rounding::round_at_impl
+//
+pub fn round_2y_s(
+    pub_keys: &PubKeySet,
+    c: &LWE,
+) -> Result<LWE, Box<dyn Error>> {
+    match pub_keys.encoder.nb_bit_precision {
+        4 => round_2y_s__pi_4(pub_keys, c),
+        5 => round_2y_s__pi_5(pub_keys, c),
+        7 => round_2y_s__pi_7(pub_keys, c),
+        pi => Err(format!("PBS round_2y_s not implemented for pi = {}.", pi).into()),
+    }
+}
 
-// zasrane, zamrdane ... http://milujupraci.cz/#29
-
-//~ pub struct Lut<'a> {
-    //~ pub title:  String,
-    //~ pub lut:    &'a (dyn Fn(f64) -> f64),
-//~ }
-
-//~ const lut_ID: dyn Fn(f64) -> f64 = |x| x;
-
-//~ pub const ID: Lut = Lut {
-    //~ title:  "Identity",
-    //~ lut:    &lut_ID,
-//~ };
-//~ pub const F_1: Lut = Lut {
-    //~ title:  "X ⋛ ±1",
-    //~ lut:    |x| x * x,
-//~ };
-//~ pub const MY_LUT: Lut = Lut {
-    //~ title:  "My LUT",
-    //~ lut:    |x| [4.,3.,2.,1.,0.,5.,6.,7.,8.,9.,10.,11.,12.,13.,14.,15.][x as usize],
-//~ };
-
-//~ pub fn with_lut(
-    //~ lut: &Lut,
-    //~ pub_keys: &PubKeySet,
-    //~ c: &LWE,
-//~ ) -> LWE {
-    //~ measure_duration!(
-        //~ ["PBS: Identity"],
-        //~ [let res = c.bootstrap_with_function(pub_keys.bsk, |x| x*x, pub_keys.encoder)?
-                    //~ .keyswitch(pub_keys.ksk)?;]
-    //~ );
-
-    //~ res
-//~ }
-
-// try LUT
-//~ let lut = |x| [1, 2, 3, 4, 5][x];
-//~ let var = 3;
-//~ println!("LUT({}) = {}", var, lut(var));
@@ -0,0 +1,164 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use rayon::prelude::*;
+use crossbeam_utils::thread;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use concrete::LWE;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::{addition,scalar_multiplication,multiplication,squaring};
+
+/// Implementation of exponentiation by a public exponent: `X^e`
+/// * right-to-left square-and-multiply: powers `X^(2^i)` are calculated by (cheaper) squaring,
+///   those selected by bits of `e` are multiplied in a parallel tree
+pub fn pow_const_impl(
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    e: u32,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if e == 0 {
+        return Ok(ParmCiphertext::single(LWE::encrypt_uint_triv(1, &pub_keys.encoder)?));
+    }
+    if e == 1 || x.len() == 0 {
+        return Ok(x.clone());
+    }
+
+    let p: ParmCiphertext;
+
+    measure_duration!(
+        ["Exponentiation ({}-bit ^ {})", x.len(), e],
+        [
+            // X^(2^i) for i up to the top bit of e
+            let mut factors: Vec<ParmCiphertext> = Vec::new();
+            let mut x_2i = x.clone();
+            for i in 0..(32 - e.leading_zeros()) {
+                if i > 0 {
+                    x_2i = squaring::squ_impl(
+                        pub_keys,
+                        &x_2i,
+                    )?;
+                }
+                if (e >> i) & 1 == 1 {
+                    factors.push(x_2i.clone());
+                }
+            }
+
+            p = prod_impl(
+                pub_keys,
+                &factors,
+            )?;
+        ]
+    );
+
+    Ok(p)
+}
+
+/// Implementation of polynomial evaluation with public coefficients: `c_0 + c_1·X + ... + c_d·X^d`
+/// * Estrin's scheme: coefficients are paired into `c_2j + c_2j+1·X` (scalar multiplications share
+///   common subexpressions), then the pairs are combined as `A_2j + A_2j+1·X^(2^k)` level by level
+/// * within a level, all products run in parallel, as well as the squaring for the next level
+pub fn poly_eval_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    coefs: &[i64],
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if coefs.len() == 0 {
+        return Ok(ParmCiphertext::empty());
+    }
+
+    let mut terms: Vec<ParmCiphertext>;
+
+    measure_duration!(
+        ["Polynomial evaluation ({}-bit, degree {})", x.len(), coefs.len() - 1],
+        [
+            // A_j = c_2j + c_2j+1·X
+            let odd: Vec<i64> = coefs.iter().skip(1).step_by(2).copied().collect();
+            let mut odd_x = scalar_multiplication::scalar_mul_many_impl(
+                pub_keys,
+                x,
+                &odd,
+            )?;
+            odd_x.resize((coefs.len() + 1) / 2, ParmCiphertext::empty());
+
+            terms = vec![ParmCiphertext::empty(); odd_x.len()];
+            terms.par_iter_mut().zip(odd_x.par_iter().enumerate()).for_each(| (tj, (j, cx)) | {
+                *tj = addition::add_const_impl(params, pub_keys, cx, coefs[2*j]).expect("addition::add_const_impl failed.");
+            });
+
+            // combine pairs with X^(2^k), k ≥ 1
+            let mut x_2k = ParmCiphertext::empty();
+            if terms.len() > 1 {
+                x_2k = squaring::squ_impl(
+                    pub_keys,
+                    x,
+                )?;
+            }
+            while terms.len() > 1 {
+                let mut next_terms = vec![ParmCiphertext::empty(); (terms.len() + 1) / 2];
+                let mut next_x_2k = ParmCiphertext::empty();
+                let (ntr, nxr) = (&mut next_terms, &mut next_x_2k);
+                let (tr, xr) = (&terms, &x_2k);
+                thread::scope(|lvl_scope| {
+                    lvl_scope.spawn(|_| {
+                        ntr.par_iter_mut().zip(tr.par_chunks(2)).for_each(| (nt, tc) | {
+                            *nt = if tc.len() == 1 {tc[0].clone()} else {
+                                let t1x = multiplication::mul_impl(pub_keys, &tc[1], xr).expect("multiplication::mul_impl failed.");
+                                addition::add_sub_noise_refresh(true, pub_keys, &tc[0], &t1x).expect("addition::add_sub_noise_refresh failed.")
+                            };
+                        });
+                    });
+                    // X^(2^(k+1)) is needed only if there is another level
+                    if tr.len() > 2 {
+                        lvl_scope.spawn(|_| {
+                            *nxr = squaring::squ_impl(pub_keys, xr).expect("squaring::squ_impl failed.");
+                        });
+                    }
+                }).expect("thread::scope lvl_scope failed.");
+
+                terms = next_terms;
+                x_2k = next_x_2k;
+            }
+        ]
+    );
+
+    Ok(terms.swap_remove(0))
+}
+
+/// Product of many ciphertexts, multiplied in a balanced binary tree (the halves in parallel)
+fn prod_impl(
+    pub_keys: &PubKeySet,
+    xs: &[ParmCiphertext],
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    match xs.len() {
+        0 => return Ok(ParmCiphertext::single(LWE::encrypt_uint_triv(1, &pub_keys.encoder)?)),
+        1 => return Ok(xs[0].clone()),
+        _ => {},
+    }
+
+    let (xs_l, xs_r) = xs.split_at(xs.len() / 2);
+    let (p_l, p_r) = rayon::join(
+        || prod_impl(pub_keys, xs_l).expect("prod_impl failed."),
+        || prod_impl(pub_keys, xs_r).expect("prod_impl failed."),
+    );
+
+    multiplication::mul_impl(
+        pub_keys,
+        &p_l,
+        &p_r,
+    )
+}
@@ -42,16 +42,28 @@ pub fn round_at_impl(
         //    -1   |-1 | 0 | 0 |
         //
         // add: 2y + s == 2, 3 .. +1 or 2y + s == -3 .. -1 otherwise 0
+        //
+        // for pi = 3, 2y + s does not fit, hence with s+ := sgn+(Z) (which is 1 for Z == 0):
+        // add: y + s+ == 2 .. +1 or y + s+ == -2 .. -1 otherwise 0
         _ => {
-            let s = signum::sgn_impl(params, pub_keys, &x[0..pos-1].to_vec())?;
-            // calc 2y
-            let mut yy_s = x[pos-1].mul_uint_constant(2)?;
-            // 2y + s
-            yy_s.add_uint_inplace(&s[0])?;
+            let r_top = if params.bit_precision == 3 {
+                let s_raw = signum::sgn_recursion_raw(params.bit_precision - 1, pub_keys, &x[0..pos-1].to_vec())?;
+                // y + s+
+                let mut y_sp = pbs::f_0__with_val(pub_keys, &s_raw[0], 1)?;
+                y_sp.add_uint_inplace(&x[pos-1])?;
+                pbs::f_2__pi_3(pub_keys, &y_sp)?
+            } else {
+                let s = signum::sgn_impl(params, pub_keys, &x[0..pos-1].to_vec())?;
+                // calc 2y
+                let mut yy_s = x[pos-1].mul_uint_constant(2)?;
+                // 2y + s
+                yy_s.add_uint_inplace(&s[0])?;
+                pbs::round_2y_s(pub_keys, &yy_s)?
+            };
 
             // factor that is to be added
             let mut r = ParmCiphertext::triv(pos, &pub_keys.encoder)?;
-            r.push(pbs::round_2y_s__pi_5(pub_keys, &yy_s)?);
+            r.push(r_top);
 
             // sliced input
             let mut slx = ParmCiphertext::triv(pos, &pub_keys.encoder)?;
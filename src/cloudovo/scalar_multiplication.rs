@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::collections::BTreeMap;
 
 //TODO add feature condition
 pub use std::fs::{self,File,OpenOptions};
@@ -6,42 +7,158 @@ pub use std::path::Path;
 pub use std::io::Write;
 use crate::*;
 
+// parallelization tools
+use rayon::prelude::*;
+
 #[allow(unused_imports)]
 use colored::Colorize;
 
 use crate::userovo::keys::PubKeySet;
 use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
-use super::addition;
+use super::{addition,shifting};
+
+/// Term of a recoded constant: `(id, shift, sign)`, i.e., `±(T_id << shift)`
+/// * `T_0 = X`, other terms are shared subexpressions
+type Term = (usize, usize, i32);
+
+/// Shared subexpression: `(a, b, d, s)`, i.e., `T = T_a + s·(T_b << d)`
+type Pattern = (usize, usize, usize, i32);
 
-/// Implementation of signum via parallel reduction
+/// Implementation of scalar multiplication `k·X`
+/// * a single-constant instance of `scalar_mul_many_impl`, hence repeated patterns in `k` are shared, too
 pub fn scalar_mul_impl(
     pub_keys: &PubKeySet,
-    k: i32,
+    k: i64,
     x: &ParmCiphertext,
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
+    let mut kx = scalar_mul_many_impl(
+        pub_keys,
+        x,
+        &[k],
+    )?;
+
+    Ok(kx.swap_remove(0))
+}
+
+/// Implementation of multiple-constant multiplication `k_0·X, k_1·X, ...`
+/// * constants are recoded (cf. `recode`), then the most frequent pair of terms is iteratively
+///   replaced by a shared subexpression, which is evaluated only once for all constants
+/// * finally, terms of each constant are summed up in a parallel tree
+pub fn scalar_mul_many_impl(
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    ks: &[i64],
+) -> Result<Vec<ParmCiphertext>, Box<dyn Error>> {
+
+    // recode constants into terms of X (sorted by shift)
+    let mut reprs: Vec<Vec<Term>> = Vec::new();
+    for k in ks {
+        let k_sgn = k.signum() as i32;
+        let mut repr: Vec<Term> = Vec::new();
+        for (i, ki) in recode(k.unsigned_abs()).iter().enumerate() {
+            if *ki != 0 {repr.push((0, i, k_sgn * ki));}
+        }
+        reprs.push(repr);
+    }
+
+    // find shared subexpressions
+    let mut subexprs: Vec<Pattern> = Vec::new();
+    loop {
+        // count all (possibly overlapping) occurrences of patterns
+        let mut counts: BTreeMap<Pattern, usize> = BTreeMap::new();
+        for repr in reprs.iter() {
+            for (ip, p) in repr.iter().enumerate() {
+                for q in repr[ip+1..].iter() {
+                    *counts.entry(pattern_of(p, q)).or_insert(0) += 1;
+                }
+            }
+        }
 
-    // move sign of k to x, prepare both +1 and -1 multiples
-    let mut x_pos = ParmCiphertext::empty();
-    let mut x_neg = ParmCiphertext::empty();
-    for xi in x {
-        if k >= 0 {
-            x_pos.push(xi.clone());
-            x_neg.push(xi.opposite_uint()?);
-        } else {
-            x_pos.push(xi.opposite_uint()?);
-            x_neg.push(xi.clone());
+        // most frequent first (ties by pattern), take the first one with ≥ 2 non-overlapping occurrences
+        let mut cands: Vec<(usize, Pattern)> = counts.into_iter().filter(|(_, c)| *c >= 2).map(|(pt, c)| (c, pt)).collect();
+        cands.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        let pt = match cands.iter().find(|(_, pt)| reprs.iter().map(|repr| match_pattern(repr, pt).len()).sum::<usize>() >= 2) {
+            Some((_, pt)) => *pt,
+            None => break,
+        };
+
+        // replace occurrences by the new term:
+        //   sa·(T_a << i) + sb·(T_b << i+d) = sa·(T_new << i)
+        let id = subexprs.len() + 1;
+        subexprs.push(pt);
+        for repr in reprs.iter_mut() {
+            let occs = match_pattern(repr, &pt);
+            if occs.len() == 0 {continue;}
+
+            let mut new_repr: Vec<Term> = Vec::new();
+            for (ip, _) in occs.iter() {
+                new_repr.push((id, repr[*ip].1, repr[*ip].2));
+            }
+            for (it, t) in repr.iter().enumerate() {
+                if !occs.iter().any(|(ip, iq)| *ip == it || *iq == it) {new_repr.push(*t);}
+            }
+            new_repr.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0)));
+            *repr = new_repr;
         }
     }
-    // from now on, only work with k_abs (the sign is already moved to x)
-    let k_abs = k.abs() as u32;
 
+    let mut res = vec![ParmCiphertext::empty(); ks.len()];
+
+    measure_duration!(
+        ["Scalar multiplication ({} constant(s), {} shared term(s) · {}-bit)", ks.len(), subexprs.len(), x.len()],
+        [
+            // evaluate shared subexpressions (those of the same level in parallel)
+            let mut levels: Vec<usize> = vec![0];
+            for (a, b, _, _) in subexprs.iter() {
+                levels.push(1 + std::cmp::max(levels[*a], levels[*b]));
+            }
+            let mut terms = vec![ParmCiphertext::empty(); subexprs.len() + 1];
+            terms[0] = x.clone();
+
+            for lvl in 1..levels.iter().max().unwrap_or(&0)+1 {
+                let ids: Vec<usize> = (1..subexprs.len()+1).filter(|id| levels[*id] == lvl).collect();
+                let vals: Vec<ParmCiphertext> = ids.par_iter().map(|id| {
+                    let (a, b, d, s) = subexprs[*id - 1];
+                    addition::add_sub_noise_refresh(
+                        s > 0,
+                        pub_keys,
+                        &terms[a],
+                        &shifting::shl_const_impl(pub_keys, &terms[b], d).expect("shifting::shl_const_impl failed."),
+                    ).expect("addition::add_sub_noise_refresh failed.")
+                }).collect();
+                for (id, val) in ids.iter().zip(vals.into_iter()) {
+                    terms[*id] = val;
+                }
+            }
+
+            // sum up terms of each constant (a single term is not bootstrapped at all)
+            res.par_iter_mut().zip(reprs.par_iter()).for_each(| (ri, repr) | {
+                let mut mulary: Vec<ParmCiphertext> = Vec::new();
+                for (id, sh, sgn) in repr.iter() {
+                    let t = if *sgn == 1 {terms[*id].clone()} else {addition::opposite_impl(&terms[*id]).expect("addition::opposite_impl failed.")};
+                    mulary.push(shifting::shl_const_impl(pub_keys, &t, *sh).expect("shifting::shl_const_impl failed."));
+                }
+                *ri = addition::sum_impl(
+                    pub_keys,
+                    &mulary,
+                ).expect("addition::sum_impl failed.");
+            });
+        ]
+    );
+
+    Ok(res)
+}
+
+/// Recode `|k|` into a vector of `{-1,0,1}` with no neighboring non-zeros
+fn recode(k_abs: u64) -> Vec<i32> {
     // resolve |k| < 2
-    if k_abs == 0 {return Ok(ParmCiphertext::empty());}
-    if k_abs == 1 {return Ok(x_pos);}
+    if k_abs < 2 {return vec![k_abs as i32];}
 
     // |k| < 2 already resolved, set to len = 2 and start from length 3: take 1 << 2 (which is 0b100 = 4)
     let mut k_len = 2usize;
-    for i in 2..31 {if k_abs & (1 << i) != 0 {k_len = i + 1;}}   //TODO as macro?
+    for i in 2..64 {if k_abs & (1 << i) != 0 {k_len = i + 1;}}   //TODO as macro?
+    // k_len might be 64, the loop below shifts by k_len
+    let k_abs = k_abs as u128;
 
     // k as a vector of bits
     // replace sequences of 1's with 1|zeros|-1
@@ -56,7 +173,7 @@ pub fn scalar_mul_impl(
     // first hit:   [-1, 0, 0, 0, 1, -1, 0, 0, 1, -1, 0, 1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 1, 0, -1, 0, 1]
     // second hit:  [-1, 0, 0, 0, -1, 0, 0, 0, -1, 0, 0, 1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 1, 0, -1, 0, 1]
     //
-    //WISH detect any reusable patterns (honestly, I do not expect much an improvement)
+    // n.b., reusable patterns are detected in scalar_mul_many_impl
 
     let mut k_vec: Vec<i32> = Vec::new();
     let mut low_1: usize = 0;
@@ -89,54 +206,36 @@ pub fn scalar_mul_impl(
         // k == 1 .. keep "pointer" at its current/previous position -> do nothing
     }
 
-    // k_len ≥ 2
-    let mut mulary: Vec<ParmCiphertext> = Vec::new();
-    for (i, ki) in k_vec.iter().enumerate() {
-        if *ki != 0 {
-            // shift x_sgn
-            let mut x_shifted = ParmCiphertext::triv(i, &pub_keys.encoder)?;
-            let mut x_cl = if *ki == 1 {x_pos.clone()} else {x_neg.clone()};   // there shall be no option other than -1, 0, +1
-            x_shifted.append(&mut x_cl);
-
-            // push shifted x_sgn to mulary
-            mulary.push(x_shifted);
-        }
-    }
-
-    // Hamming weight of k is 1
-    if mulary.len() == 1 {
-        return Ok(mulary[0].clone());
-    }
+    k_vec
+}
 
-    //TODO
-    //  since there are no subsequent lines of len & len+1 (follows from the fact that there are no neighboring non-zeros in optimized k_vec),
-    //  this mulary does not need to be reduced sequentially, most of it can be done in parallel (carefully; the last row must be added in the last step)
+/// Pattern of two terms, where `q` is not shifted less than `p`
+fn pattern_of(
+    p: &Term,
+    q: &Term,
+) -> Pattern {
+    (p.0, q.0, q.1 - p.1, p.2 * q.2)
+}
 
-    // reduce mulary
-    measure_duration!(
-        ["Scalar multiplication (non-triv ±{} · {}-bit)", k_abs, x.len()],
-        [
-            // reduce multiplication array (of length ≥ 2)
-            let mut intmd = vec![ParmCiphertext::empty(); 2];
-            let mut idx = 0usize;
-            intmd[idx] = addition::add_sub_noise_refresh(
-                true,
-                pub_keys,
-                &mulary[0],
-                &mulary[1],
-            )?;
-
-            for i in 2..mulary.len() {
-                idx ^= 1;
-                intmd[idx] = addition::add_sub_noise_refresh(
-                    true,
-                    pub_keys,
-                    &intmd[idx ^ 1],
-                    &mulary[i],
-                )?;
+/// Greedily match non-overlapping occurrences of a pattern in a recoded constant
+fn match_pattern(
+    repr: &Vec<Term>,
+    pt: &Pattern,
+) -> Vec<(usize, usize)> {
+    let mut used = vec![false; repr.len()];
+    let mut occs: Vec<(usize, usize)> = Vec::new();
+
+    for ip in 0..repr.len() {
+        if used[ip] {continue;}
+        for iq in ip+1..repr.len() {
+            if !used[iq] && pattern_of(&repr[ip], &repr[iq]) == *pt {
+                used[ip] = true;
+                used[iq] = true;
+                occs.push((ip, iq));
+                break;
             }
-        ]
-    );
+        }
+    }
 
-    Ok(intmd[idx].clone())
+    occs
 }
@@ -0,0 +1,117 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use rayon::prelude::*;
+use crossbeam_utils::thread;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use concrete::LWE;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::pbs;
+
+/// Implementation of conditional select (multiplexer): `c ? X : Y`
+/// * `c` is an encrypted bit in `{0,1}` (e.g., result of comparison)
+/// * empty `c` is considered zero
+pub fn select_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    c: &ParmCiphertext,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    if c.len() > 1 {
+        return Err(format!("Condition of select must be a single encrypted bit, got {} words.", c.len()).into());
+    }
+    // resolve empty condition
+    if c.len() == 0 {
+        return Ok(y.clone());
+    }
+
+    let m: ParmCiphertext;
+
+    measure_duration!(
+        ["Select ({}-bit)", std::cmp::max(x.len(), y.len())],
+        [
+            // s = ±2 (in particular: 2 for c == 1, -2 for c == 0)
+            let lut = pbs::Lut::new(params.bit_precision, 0..=1, |b| 4 * b - 2)?;
+            let s_2: LWE = pbs::eval_lut(pub_keys, &c[0], &lut)?;
+
+            m = select_by_s2(
+                params,
+                pub_keys,
+                &s_2,
+                x,
+                y,
+            )?;
+        ]
+    );
+
+    Ok(m)
+}
+
+/// Select `X` for `s_2 == 2`, or `Y` for `s_2 == -2` (word-wise, in parallel)
+pub fn select_by_s2(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    s_2: &LWE,
+    x: &ParmCiphertext,
+    y: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // relu_plus does not exist for pi = 3
+    if params.bit_precision < 4 {
+        return Err(format!("Select not implemented for pi = {}.", params.bit_precision).into());
+    }
+
+    // align inputs
+    let mut xa = x.clone();
+    let mut ya = y.clone();
+    for _ in 0..((y.len() as i64) - (x.len() as i64)) {
+        xa.push(LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
+    }
+    for _ in 0..((x.len() as i64) - (y.len() as i64)) {
+        ya.push(LWE::encrypt_uint_triv(0, &pub_keys.encoder)?);
+    }
+
+    let mut m = ParmCiphertext::triv(xa.len(), &pub_keys.encoder)?;
+
+    // calc x and y selectors
+    m.par_iter_mut().zip(xa.par_iter().zip(ya.par_iter())).for_each(| (mi, (xi, yi)) | {
+        // xi + 2s
+        let xi_p2s: LWE = xi.add_uint(s_2).expect("add_uint failed.");
+        // yi - 2s
+        let yi_n2s: LWE = yi.sub_uint(s_2).expect("sub_uint failed.");
+
+        // t, u (in parallel)
+        // init tmp variables in this scope, only references can be passed to threads
+        let mut ui = LWE::encrypt_uint_triv(0, &pub_keys.encoder).expect("LWE::encrypt_uint_triv failed.");
+        let uir = &mut ui;
+
+        // parallel pool: mi, ui
+        thread::scope(|miui_scope| {
+            miui_scope.spawn(|_| {
+                // mi = ReLU+(xi + 2s)
+                *mi    = pbs::relu_plus(pub_keys, &xi_p2s).expect("pbs::relu_plus failed.");   // ti
+            });
+            miui_scope.spawn(|_| {
+                // ui = ReLU+(yi + 2s)
+                *uir   = pbs::relu_plus(pub_keys, &yi_n2s).expect("pbs::relu_plus failed.");
+            });
+        }).expect("thread::scope miui_scope failed.");
+
+        // t + u
+        mi.add_uint_inplace(&ui).expect("add_uint_inplace failed.");
+    });
+
+    Ok(m)
+}
@@ -0,0 +1,208 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use rayon::prelude::*;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::{pbs,addition,comparison,rounding,selection};
+use super::comparison::CmpOp;
+
+/// Maximum length of encrypted shift amount (the barrel shifter makes up to `2^len` words)
+pub const SHIFT_AMOUNT_MAX_LEN: usize = 8;
+
+/// Rounding mode of right shift:
+/// * `FLOOR`: towards `-∞` (as `>>` on Rust integers),
+/// * `ROUND`: to the nearest, halves up (cf. `round_at`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoundMode {
+    FLOOR,
+    ROUND,
+}
+
+/// Implementation of left shift by a public amount: `X · 2^k`
+pub fn shl_const_impl(
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    k: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve empty x
+    if x.len() == 0 {
+        return Ok(ParmCiphertext::empty());
+    }
+
+    let mut s = ParmCiphertext::triv(k, &pub_keys.encoder)?;
+    s.extend(x.iter().cloned());
+
+    Ok(s)
+}
+
+/// Implementation of right shift by a public amount: `X / 2^k`, rounded according to `mode`
+pub fn shr_const_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    k: usize,
+    mode: RoundMode,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if k == 0 || x.len() == 0 {
+        return Ok(x.clone());
+    }
+
+    let s: ParmCiphertext;
+
+    measure_duration!(
+        ["Right shift by {} ({:?}, {}-bit)", k, mode, x.len()],
+        [
+            match mode {
+                RoundMode::ROUND => {
+                    let xr = rounding::round_at_impl(
+                        params,
+                        pub_keys,
+                        x,
+                        k,
+                    )?;
+                    s = if xr.len() > k {xr[k..].to_vec()} else {ParmCiphertext::empty()};
+                },
+                RoundMode::FLOOR => {
+                    // X = H·2^k + L, where |L| < 2^k, hence floor(X / 2^k) = H - (L < 0)
+                    let (l, h) = if x.len() > k {(x[..k].to_vec(), x[k..].to_vec())} else {(x.clone(), ParmCiphertext::empty())};
+                    let c = comparison::cmp_const_impl(
+                        params,
+                        pub_keys,
+                        CmpOp::LT,
+                        &l,
+                        0,
+                    )?;
+                    s = addition::add_sub_impl(
+                        false,
+                        pub_keys,
+                        &h,
+                        &c,
+                    )?;
+                },
+            }
+        ]
+    );
+
+    Ok(s)
+}
+
+/// Implementation of left shift by an encrypted amount: `X · 2^K`
+/// * `K` must be non-negative (for `K < 0`, the result is `X / 2^|K|` truncated word-wise)
+/// * barrel shifter: shifts by `(k_j + 1)·2^j` for each word `k_j ∈ {-1,0,1}` of `K`,
+///   then drops the offset `2^m - 1` (which holds encrypted zeros), where `m` is the length of `K`
+pub fn shl_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    k: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if k.len() == 0 || x.len() == 0 {
+        return Ok(x.clone());
+    }
+
+    let s: ParmCiphertext;
+
+    measure_duration!(
+        ["Left shift by encrypted amount ({}-bit by {}-bit)", x.len(), k.len()],
+        [
+            let xs = barrel_impl(params, pub_keys, x, k, false)?;
+            s = xs[(1 << k.len()) - 1..].to_vec();
+        ]
+    );
+
+    Ok(s)
+}
+
+/// Implementation of right shift by an encrypted amount: `X / 2^K`, rounded according to `mode`
+/// * `K` must be non-negative
+/// * shifts left by `2^m - 1 - K` (i.e., by `(1 - k_j)·2^j` for each word of `K`),
+///   then right by public `2^m - 1`, where `m` is the length of `K`
+pub fn shr_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    k: &ParmCiphertext,
+    mode: RoundMode,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if k.len() == 0 || x.len() == 0 {
+        return Ok(x.clone());
+    }
+
+    let s: ParmCiphertext;
+
+    measure_duration!(
+        ["Right shift by encrypted amount ({:?}, {}-bit by {}-bit)", mode, x.len(), k.len()],
+        [
+            let xs = barrel_impl(params, pub_keys, x, k, true)?;
+            s = shr_const_impl(params, pub_keys, &xs, (1 << k.len()) - 1, mode)?;
+        ]
+    );
+
+    Ok(s)
+}
+
+/// Barrel shifter: shifts `X` left by `(1 ± k_j)·2^j` for each word `k_j` of `K`, i.e., by `2^m - 1 ± K` in total
+/// * sign is `-` if `neg`
+/// * selectors only depend on `K`, hence all of them take a single layer of bootstraps, only selects are sequential
+fn barrel_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    k: &ParmCiphertext,
+    neg: bool,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // relu_plus does not exist for pi = 3
+    if params.bit_precision < 4 {
+        return Err(format!("Shift by encrypted amount not implemented for pi = {}.", params.bit_precision).into());
+    }
+    // up to 2^m words added
+    if k.len() > SHIFT_AMOUNT_MAX_LEN {
+        return Err(format!("Shift by encrypted amount of length {} not supported (at most {} words).", k.len(), SHIFT_AMOUNT_MAX_LEN).into());
+    }
+
+    // selectors: e_j ≥ 0, e_j == 1, where e_j = ±k_j
+    let sgn_e: i32 = if neg {-1} else {1};
+    let lut_ge_0 = pbs::Lut::new(params.bit_precision, -1..=1, |kj| if sgn_e * kj >= 0 {2} else {-2})?;
+    let lut_eq_1 = pbs::Lut::new(params.bit_precision, -1..=1, |kj| if sgn_e * kj == 1 {2} else {-2})?;
+
+    // selectors of all words at once (in parallel), they only depend on k_j
+    let mut s_ge_0 = ParmCiphertext::triv(k.len(), &pub_keys.encoder)?;
+    let mut s_eq_1 = ParmCiphertext::triv(k.len(), &pub_keys.encoder)?;
+    s_ge_0.par_iter_mut().zip(s_eq_1.par_iter_mut()).zip(k.par_iter()).for_each(| ((s_ge_0j, s_eq_1j), kj) | {
+        let (s_g, s_e) = rayon::join(
+            || pbs::eval_lut(pub_keys, kj, &lut_ge_0).expect("pbs::eval_lut failed."),
+            || pbs::eval_lut(pub_keys, kj, &lut_eq_1).expect("pbs::eval_lut failed."),
+        );
+        *s_ge_0j = s_g;
+        *s_eq_1j = s_e;
+    });
+
+    let mut s = x.clone();
+
+    for j in 0..k.len() {
+        // shifted by 2^j and 2^(j+1)
+        let s_1 = shl_const_impl(pub_keys, &s, 1 << j)?;
+        let s_2 = shl_const_impl(pub_keys, &s, 1 << (j + 1))?;
+
+        // e_j == 1 ? S·2^(j+1) : (e_j ≥ 0 ? S·2^j : S)
+        let t = selection::select_by_s2(params, pub_keys, &s_ge_0[j], &s_1, &s)?;
+        s = selection::select_by_s2(params, pub_keys, &s_eq_1[j], &s_2, &t)?;
+    }
+
+    Ok(s)
+}
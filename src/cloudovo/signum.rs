@@ -39,7 +39,7 @@ pub fn sgn_impl(
                 x,
             )?;
 
-            let s_lwe = pbs::f_1__pi_5__with_val(
+            let s_lwe = pbs::f_1__with_val(
                 pub_keys,
                 &s_raw[0],
                 1,
@@ -79,11 +79,11 @@ pub fn sgn_recursion_raw(
 
                 sj.par_iter_mut().enumerate().for_each(| (i, sji) | {
                     if gamma * j + i < x.len() {
-                        *sji = pbs::f_1__pi_5__with_val(
+                        *sji = pbs::f_1__with_val(
                             pub_keys,
                             &x[gamma * j + i],
                             1 << i,
-                        ).expect("pbs::f_1__pi_5__with_val failed.");
+                        ).expect("pbs::f_1__with_val failed.");
                     }
                 });
 
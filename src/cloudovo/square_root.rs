@@ -0,0 +1,92 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use concrete::LWE;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::{addition,comparison,selection,width};
+use super::comparison::CmpOp;
+
+/// Implementation of integer square root: `⌊√X⌋` (zero for negative `X`)
+/// * digit-by-digit (restoring) method: for `i = m-1 .. 0`, where `m = ⌈n/2⌉`,
+///   `T = Q·2^(i+1) + 2^(2i)` is subtracted from the remainder `R` (initially `X`),
+///   if `R - T ≥ 0`, bit `q_i` of `Q` is set and `R` is replaced by `R - T`
+/// * `T` needs no addition: `Q` only holds bits above `i`, hence `2^(2i)` falls below `Q·2^(i+1)`
+/// * after step `i`, `0 ≤ R < 2^(i+1)·Q + 2^(2i) < 2^(m+i+1)`, hence `R` is trimmed to `m + i + 2` words
+///   (otherwise, it would grow by a carry word in each step)
+/// * result has `m` words in `{0,1}`
+pub fn isqrt_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if x.len() == 0 {
+        return Ok(ParmCiphertext::empty());
+    }
+
+    let m = (x.len() + 1) / 2;
+    let mut q = ParmCiphertext::triv(m, &pub_keys.encoder)?;
+
+    measure_duration!(
+        ["Square root ({}-bit)", x.len()],
+        [
+            let mut r = x.clone();
+
+            for i in (0..m).rev() {
+                // T = [0 .. 0 (2i words), 1, 0, q_(i+1), .., q_(m-1)]
+                let mut t = ParmCiphertext::triv(2*i + 2, &pub_keys.encoder)?;
+                t[2*i] = LWE::encrypt_uint_triv(1, &pub_keys.encoder)?;
+                t.extend(q[i+1..].iter().cloned());
+
+                // D = R - T
+                let d = addition::add_sub_impl(
+                    false,
+                    pub_keys,
+                    &r,
+                    &t,
+                )?;
+
+                // q_i = D ≥ 0
+                let qi = comparison::cmp_const_impl(
+                    params,
+                    pub_keys,
+                    CmpOp::GE,
+                    &d,
+                    0,
+                )?;
+
+                // the remainder is not needed after the last bit
+                if i > 0 {
+                    let r_sel = selection::select_impl(
+                        params,
+                        pub_keys,
+                        &qi,
+                        &d,
+                        &r,
+                    )?;
+                    r = width::trim_nonneg_impl(
+                        params,
+                        pub_keys,
+                        &r_sel,
+                        m + i + 1,
+                    )?;
+                }
+
+                q[i] = qi[0].clone();
+            }
+        ]
+    );
+
+    Ok(q)
+}
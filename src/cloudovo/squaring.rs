@@ -25,27 +25,35 @@ use super::{pbs,addition,multiplication};
 //  Squaring
 //
 
+/// Shortest length, for which Divide'n'Conquer is used (shorter operands are squared by schoolbook)
+pub const SQU_DNQ_MIN_LEN: usize = 4;
+
 /// Choose & call appropriate algorithm for a square of a ciphertexts (Divide'n'Conquer, or schoolbook multiplication)
 pub fn squ_impl(
     pub_keys: &PubKeySet,
     x: &ParmCiphertext,
 ) -> Result<ParmCiphertext, Box<dyn Error>> {
 
+    // |X| ≥ 2 does not exist for pi = 3 (needed in multiplication of distinct words)
+    if pub_keys.encoder.nb_bit_precision < 4 {
+        return Err(format!("Squaring not implemented for pi = {}.", pub_keys.encoder.nb_bit_precision).into());
+    }
+
     let s = match x.len() {
         l if l == 0 => ParmCiphertext::triv(1, &pub_keys.encoder)?,
         l if l == 1 => squ_1word(
             pub_keys,
             x,
         )?,
-        l if l < 4 => squ_schoolbook(
+        l if l < SQU_DNQ_MIN_LEN => squ_schoolbook(
             pub_keys,
             x,
         )?,
-        l if l <= 32 => squ_dnq(
+        // recurs into squ_impl & mul_impl, hence there is no limit on length
+        _ => squ_dnq(
             pub_keys,
             x,
         )?,
-        _ => return Err(format!("Squaring for {}-word integer not implemented.", x.len()).into()),
     };
 
     Ok(s)
@@ -190,29 +198,14 @@ fn squ_schoolbook(
             )?;
 
             // reduce squaring array
-            //TODO write a function that will be common with scalar_multiplication (if this is possible with strategies 2+)
-            let mut intmd = vec![ParmCiphertext::empty(); 2];
-            let mut idx = 0usize;
-            intmd[idx] = addition::add_sub_noise_refresh(
-                true,
+            let s = addition::sum_impl(
                 pub_keys,
-                &squary[0],
-                &squary[1],
+                &squary,
             )?;
-
-            for i in 2..x.len() {
-                idx ^= 1;
-                intmd[idx] = addition::add_sub_noise_refresh(
-                    true,
-                    pub_keys,
-                    &intmd[idx ^ 1],
-                    &squary[i],
-                )?;
-            }
         ]
     );
 
-    Ok(intmd[idx].clone())
+    Ok(s)
 }
 
 /// Square of a 1-word ciphertext
@@ -279,5 +272,5 @@ pub fn squ_lwe(
     pub_keys: &PubKeySet,
     x: &LWE,
 ) -> Result<LWE, Box<dyn Error>> {
-    Ok(pbs::a_1__pi_5(pub_keys, x)?)
+    Ok(pbs::a_1(pub_keys, x)?)
 }
@@ -0,0 +1,179 @@
+use std::error::Error;
+
+//TODO add feature condition
+pub use std::fs::{self,File,OpenOptions};
+pub use std::path::Path;
+pub use std::io::Write;
+use crate::*;
+
+// parallelization tools
+use crossbeam_utils::thread;
+
+#[allow(unused_imports)]
+use colored::Colorize;
+
+use crate::params::Params;
+use crate::userovo::keys::PubKeySet;
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use super::{addition,signum,comparison,selection,conversion};
+use super::comparison::CmpOp;
+
+/// Implementation of wrapping truncation into `words` words: `X mod 2^n` in `[-2^(n-1), 2^(n-1))`
+/// * i.e., as wrapping arithmetics on `n`-bit two's complement integers
+/// * `X` is converted into canonical two's complement, lowest `n` bits are taken (the top one being the sign bit),
+///   unless `X` is shorter than `n` (then it fits as it is)
+/// * result has exactly `n` words
+pub fn truncate_wrap_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    words: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if words == 0 {
+        return Ok(ParmCiphertext::empty());
+    }
+    if x.len() < words {
+        let mut xp = x.clone();
+        xp.append(&mut ParmCiphertext::triv(words - x.len(), &pub_keys.encoder)?);
+        return Ok(xp);
+    }
+
+    let t: ParmCiphertext;
+
+    measure_duration!(
+        ["Wrapping truncation ({}-bit into {} words)", x.len(), words],
+        [
+            // x.len() + 1 bits, the top one is the sign bit
+            let c = conversion::to_canonical_impl(
+                params,
+                pub_keys,
+                x,
+            )?;
+
+            t = conversion::from_canonical_impl(&c[..words].to_vec())?;
+        ]
+    );
+
+    Ok(t)
+}
+
+/// Implementation of saturation: `min{max{X, min}, max}`
+/// * result has the length of the longest of `X`, `min` and `max`
+pub fn saturate_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    min: i64,
+    max: i64,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    if min > max {
+        return Err(format!("Saturation into empty range [{}, {}].", min, max).into());
+    }
+
+    let s: ParmCiphertext;
+
+    measure_duration!(
+        ["Saturation ({}-bit into [{}, {}])", x.len(), min, max],
+        [
+            // c_lo = X < min, c_hi = X > max (in parallel)
+            let mut c_lo = ParmCiphertext::empty();
+            let mut c_hi = ParmCiphertext::empty();
+            let (c_lor, c_hir) = (&mut c_lo, &mut c_hi);
+            thread::scope(|c_scope| {
+                c_scope.spawn(|_| {
+                    *c_lor = comparison::cmp_const_impl(params, pub_keys, CmpOp::LT, x, min).expect("comparison::cmp_const_impl failed.");
+                });
+                c_scope.spawn(|_| {
+                    *c_hir = comparison::cmp_const_impl(params, pub_keys, CmpOp::GT, x, max).expect("comparison::cmp_const_impl failed.");
+                });
+            }).expect("thread::scope c_scope failed.");
+
+            // trivial samples of the bounds
+            let c_min = addition::add_const_impl(params, pub_keys, &ParmCiphertext::empty(), min)?;
+            let c_max = addition::add_const_impl(params, pub_keys, &ParmCiphertext::empty(), max)?;
+
+            // the conditions are exclusive
+            let s_hi = selection::select_impl(
+                params,
+                pub_keys,
+                &c_hi,
+                &c_max,
+                x,
+            )?;
+            s = selection::select_impl(
+                params,
+                pub_keys,
+                &c_lo,
+                &c_min,
+                &s_hi,
+            )?;
+        ]
+    );
+
+    Ok(s)
+}
+
+/// Implementation of saturation into `words` words: `X` clamped to `[-2^(n-1), 2^(n-1))`
+/// * the same range as of `truncate_wrap_impl`, result has exactly `n` words
+pub fn clamp_to_words_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    words: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // resolve trivial cases
+    if words == 0 {
+        return Ok(ParmCiphertext::empty());
+    }
+    // X fits (as the i64 reference for words ≥ 64)
+    if x.len() < words {
+        return truncate_wrap_impl(params, pub_keys, x, words);
+    }
+    if words > 63 {
+        return Err(format!("Saturation into {} words not supported (bounds do not fit i64).", words).into());
+    }
+
+    let s = saturate_impl(
+        params,
+        pub_keys,
+        x,
+        -(1i64 << (words - 1)),
+        (1i64 << (words - 1)) - 1,
+    )?;
+
+    // exact, since S fits
+    truncate_wrap_impl(
+        params,
+        pub_keys,
+        &s,
+        words,
+    )
+}
+
+/// Implementation of trimming of a non-negative `X < 2^n` into `n + 1` words (e.g., a remainder with a known bound)
+/// * the condition is not checked
+/// * with `L` the lowest `n` words, the top words hold `H = (X - L) / 2^n`, which is `0` or `1` (since `|L| < 2^n`),
+///   hence `H = sgn(top words)` (a single word), i.e., only top words are bootstrapped
+pub fn trim_nonneg_impl(
+    params: &Params,
+    pub_keys: &PubKeySet,
+    x: &ParmCiphertext,
+    words: usize,
+) -> Result<ParmCiphertext, Box<dyn Error>> {
+    // nothing to save
+    if x.len() <= words + 1 {
+        return Ok(x.clone());
+    }
+
+    let h = signum::sgn_impl(
+        params,
+        pub_keys,
+        &x[words..].to_vec(),
+    )?;
+
+    let mut t = x[..words].to_vec();
+    t.push(h[0].clone());
+
+    Ok(t)
+}
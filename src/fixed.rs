@@ -0,0 +1,687 @@
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt, ParmFixed};
+use crate::ParmesanCloudovo;
+use crate::arithmetics::ParmArithmetics;
+use crate::cloudovo::*;
+use crate::cloudovo::comparison::CmpOp;
+use crate::cloudovo::shifting::RoundMode;
+
+
+// =============================================================================
+//
+//  Parmesan Fixed-Point Arithmetics
+//
+//  * `ParmArithmetics` is implemented for `ParmFixed`, and for `f64` as a reference
+//    (for accuracy comparisons), hence generic code (e.g., `NeuralNetwork::eval`) runs over both
+//  * integer constants (e.g., in `add_const`, `cmp_const`) are taken at the scale of `X`
+//  * products & right shifts are rounded to the scale of `X` (the greater one), while the reference is exact
+//  * operations on words (e.g., bitwise) are in `ParmWordArithmetics`, which is not implemented here
+//
+
+/// Parmesan Fixed-Point Arithmetics Trait (on top of `ParmArithmetics`)
+/// * implemented for `ParmFixed`, and for `f64` as a reference (for accuracy comparisons)
+pub trait ParmFixedArithmetics {
+    /// Rescaling: `X` rounded to `scale` fractional bits
+    fn rescale(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        scale: usize,
+    ) -> Self;
+}
+
+impl ParmFixedArithmetics for f64 {
+    fn rescale(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        scale: usize,
+    ) -> f64 {
+        // to the nearest, halves up (as round_at)
+        let s = (scale as f64).exp2();
+        (x * s + 0.5).floor() / s
+    }
+}
+
+impl ParmFixedArithmetics for ParmFixed {
+    fn rescale(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        scale: usize,
+    ) -> ParmFixed {
+        fixed_point::rescale_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            scale,
+        ).expect("ParmFixedArithmetics::rescale failed.")
+    }
+}
+
+impl ParmArithmetics for f64 {
+    fn zero() -> f64 {0.0}
+
+    fn opp(x: &f64) -> f64 {-x}
+
+    fn add(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {x + y}
+
+    fn sub(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {x - y}
+
+    fn add_const(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        k: i64,
+    ) -> f64 {x + k as f64}
+
+    fn sum(
+        _pc: &ParmesanCloudovo,
+        a: &[f64],
+    ) -> f64 {a.iter().sum()}
+
+    fn scalar_mul(
+        _pc: &ParmesanCloudovo,
+        k: i64,
+        x: &f64,
+    ) -> f64 {k as f64 * x}
+
+    fn scalar_mul_many(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        ks: &[i64],
+    ) -> Vec<f64> {ks.iter().map(|k| *k as f64 * x).collect()}
+
+    fn sgn(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+    ) -> f64 {
+        // n.b., f64::signum(0.0) is 1.0
+        if *x > 0.0 {1.0} else if *x < 0.0 {-1.0} else {0.0}
+    }
+
+    fn max(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {x.max(*y)}
+
+    fn min(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {x.min(*y)}
+
+    fn abs(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+    ) -> f64 {x.abs()}
+
+    fn argmax(
+        _pc: &ParmesanCloudovo,
+        a: &[f64],
+    ) -> (f64, f64) {
+        let mut im = 0usize;
+        for (i, ai) in a.iter().enumerate() {
+            if *ai > a[im] {im = i;}
+        }
+        (im as f64, a[im])
+    }
+
+    fn relu(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+    ) -> f64 {x.max(0.0)}
+
+    fn mul(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {x * y}
+
+    fn squ(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+    ) -> f64 {x * x}
+
+    fn pow_const(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        e: u32,
+    ) -> f64 {x.powi(e as i32)}
+
+    fn poly_eval(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        coefs: &[i64],
+    ) -> f64 {
+        // Horner's scheme
+        coefs.iter().rev().fold(0.0, |acc, c| acc * x + *c as f64)
+    }
+
+    fn div_rem(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> (f64, f64) {
+        if *y == 0.0 {(0.0, *x)} else {((x / y).trunc(), x % y)}
+    }
+
+    fn div_rem_const(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        d: i64,
+    ) -> (f64, f64) {(x.div_euclid(d as f64), x.rem_euclid(d as f64))}
+
+    fn isqrt(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+    ) -> f64 {
+        if *x <= 0.0 {0.0} else {x.sqrt().floor()}
+    }
+
+    fn round_at(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        pos: usize,
+    ) -> f64 {
+        // to a multiple of 2^pos, halves up
+        let s = (pos as f64).exp2();
+        (x / s + 0.5).floor() * s
+    }
+
+    fn shl_const(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        k: usize,
+    ) -> f64 {x * (k as f64).exp2()}
+
+    fn shr_const(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        k: usize,
+        _mode: RoundMode,
+    ) -> f64 {x / (k as f64).exp2()}
+
+    fn shl(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        k: &f64,
+    ) -> f64 {x * k.exp2()}
+
+    fn shr(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        k: &f64,
+        _mode: RoundMode,
+    ) -> f64 {x / k.exp2()}
+
+    fn saturate(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        min: i64,
+        max: i64,
+    ) -> f64 {x.max(min as f64).min(max as f64)}
+
+    fn cmp(
+        _pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {
+        let s = if x < y {-1} else if x > y {1} else {0};
+        op.eval_plain(s, 0) as i64 as f64
+    }
+
+    fn cmp_const(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &f64,
+        k: i64,
+    ) -> f64 {Self::cmp(pc, op, x, &(k as f64))}
+
+    fn select(
+        _pc: &ParmesanCloudovo,
+        c: &f64,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {if *c != 0.0 {*x} else {*y}}
+}
+
+impl ParmArithmetics for ParmFixed {
+    fn zero() -> ParmFixed {
+        ParmFixed {ct: ParmCiphertext::empty(), scale: 0}
+    }
+
+    fn opp(x: &ParmFixed) -> ParmFixed {
+        ParmFixed {
+            ct: addition::opposite_impl(&x.ct).expect("ParmArithmetics::opp failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn add(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        fixed_point::add_sub_impl(
+            true,
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmArithmetics::add failed.")
+    }
+
+    fn sub(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        fixed_point::add_sub_impl(
+            false,
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmArithmetics::sub failed.")
+    }
+
+    fn add_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        k: i64,
+    ) -> ParmFixed {
+        fixed_point::add_const_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            k,
+        ).expect("ParmArithmetics::add_const failed.")
+    }
+
+    fn sum(
+        pc: &ParmesanCloudovo,
+        a: &[ParmFixed],
+    ) -> ParmFixed {
+        let ar: Vec<&ParmFixed> = a.iter().collect();
+        let (cts, scale) = fixed_point::align_impl(pc.params, pc.pub_keys, &ar).expect("ParmArithmetics::sum failed.");
+        ParmFixed {
+            ct: addition::sum_impl(
+                pc.pub_keys,
+                &cts,
+            ).expect("ParmArithmetics::sum failed."),
+            scale,
+        }
+    }
+
+    fn scalar_mul(
+        pc: &ParmesanCloudovo,
+        k: i64,
+        x: &ParmFixed,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: scalar_multiplication::scalar_mul_impl(
+                pc.pub_keys,
+                k,
+                &x.ct,
+            ).expect("ParmArithmetics::scalar_mul failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn scalar_mul_many(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        ks: &[i64],
+    ) -> Vec<ParmFixed> {
+        scalar_multiplication::scalar_mul_many_impl(
+            pc.pub_keys,
+            &x.ct,
+            ks,
+        ).expect("ParmArithmetics::scalar_mul_many failed.")
+            .into_iter().map(|ct| ParmFixed {ct, scale: x.scale}).collect()
+    }
+
+    fn sgn(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: signum::sgn_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+            ).expect("ParmArithmetics::sgn failed."),
+            scale: 0,
+        }
+    }
+
+    fn max(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        let (xy, scale) = fixed_point::align_impl(pc.params, pc.pub_keys, &[x, y]).expect("ParmArithmetics::max failed.");
+        ParmFixed {
+            ct: maximum::max_impl(
+                pc.params,
+                pc.pub_keys,
+                &xy[0],
+                &xy[1],
+            ).expect("ParmArithmetics::max failed."),
+            scale,
+        }
+    }
+
+    fn min(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        let (xy, scale) = fixed_point::align_impl(pc.params, pc.pub_keys, &[x, y]).expect("ParmArithmetics::min failed.");
+        ParmFixed {
+            ct: maximum::min_impl(
+                pc.params,
+                pc.pub_keys,
+                &xy[0],
+                &xy[1],
+            ).expect("ParmArithmetics::min failed."),
+            scale,
+        }
+    }
+
+    fn abs(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: maximum::abs_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+            ).expect("ParmArithmetics::abs failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn argmax(
+        pc: &ParmesanCloudovo,
+        a: &[ParmFixed],
+    ) -> (ParmFixed, ParmFixed) {
+        let ar: Vec<&ParmFixed> = a.iter().collect();
+        let (cts, scale) = fixed_point::align_impl(pc.params, pc.pub_keys, &ar).expect("ParmArithmetics::argmax failed.");
+        let (i, m) = maximum::argmax_impl(
+            pc.params,
+            pc.pub_keys,
+            &cts,
+        ).expect("ParmArithmetics::argmax failed.");
+        (ParmFixed {ct: i, scale: 0}, ParmFixed {ct: m, scale})
+    }
+
+    fn relu(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: maximum::max_impl(
+                pc.params,
+                pc.pub_keys,
+                &ParmCiphertext::empty(),
+                &x.ct,
+            ).expect("ParmArithmetics::relu failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn mul(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        fixed_point::mul_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmArithmetics::mul failed.")
+    }
+
+    fn squ(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+    ) -> ParmFixed {
+        fixed_point::squ_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+        ).expect("ParmArithmetics::squ failed.")
+    }
+
+    fn pow_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        e: u32,
+    ) -> ParmFixed {
+        fixed_point::pow_const_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            e,
+        ).expect("ParmArithmetics::pow_const failed.")
+    }
+
+    fn poly_eval(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        coefs: &[i64],
+    ) -> ParmFixed {
+        // Horner's scheme (each product is rounded to the scale of X)
+        coefs.iter().rev().fold(Self::zero(), |acc, c| Self::add_const(pc, &Self::mul(pc, &acc, x), *c))
+    }
+
+    fn div_rem(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> (ParmFixed, ParmFixed) {
+        fixed_point::div_rem_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmArithmetics::div_rem failed.")
+    }
+
+    fn div_rem_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        d: i64,
+    ) -> (ParmFixed, ParmFixed) {
+        fixed_point::div_rem_const_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            d,
+        ).expect("ParmArithmetics::div_rem_const failed.")
+    }
+
+    fn isqrt(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+    ) -> ParmFixed {
+        fixed_point::isqrt_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+        ).expect("ParmArithmetics::isqrt failed.")
+    }
+
+    fn round_at(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        pos: usize,
+    ) -> ParmFixed {
+        // pos refers to the value, i.e., to the integer part
+        ParmFixed {
+            ct: rounding::round_at_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+                pos + x.scale,
+            ).expect("ParmArithmetics::round_at failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn shl_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        k: usize,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: shifting::shl_const_impl(
+                pc.pub_keys,
+                &x.ct,
+                k,
+            ).expect("ParmArithmetics::shl_const failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn shr_const(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        k: usize,
+        mode: RoundMode,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: shifting::shr_const_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+                k,
+                mode,
+            ).expect("ParmArithmetics::shr_const failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn shl(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        k: &ParmFixed,
+    ) -> ParmFixed {
+        // the shift amount must be an integer
+        let k_int = Self::rescale(pc, k, 0);
+        ParmFixed {
+            ct: shifting::shl_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+                &k_int.ct,
+            ).expect("ParmArithmetics::shl failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn shr(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        k: &ParmFixed,
+        mode: RoundMode,
+    ) -> ParmFixed {
+        // the shift amount must be an integer
+        let k_int = Self::rescale(pc, k, 0);
+        ParmFixed {
+            ct: shifting::shr_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+                &k_int.ct,
+                mode,
+            ).expect("ParmArithmetics::shr failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn saturate(
+        pc: &ParmesanCloudovo,
+        x: &ParmFixed,
+        min: i64,
+        max: i64,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: width::saturate_impl(
+                pc.params,
+                pc.pub_keys,
+                &x.ct,
+                fixed_point::const_at_scale(min, x.scale).expect("ParmArithmetics::saturate failed."),
+                fixed_point::const_at_scale(max, x.scale).expect("ParmArithmetics::saturate failed."),
+            ).expect("ParmArithmetics::saturate failed."),
+            scale: x.scale,
+        }
+    }
+
+    fn cmp(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        let (xy, _) = fixed_point::align_impl(pc.params, pc.pub_keys, &[x, y]).expect("ParmArithmetics::cmp failed.");
+        ParmFixed {
+            ct: comparison::cmp_impl(
+                pc.params,
+                pc.pub_keys,
+                op,
+                &xy[0],
+                &xy[1],
+            ).expect("ParmArithmetics::cmp failed."),
+            scale: 0,
+        }
+    }
+
+    fn cmp_const(
+        pc: &ParmesanCloudovo,
+        op: CmpOp,
+        x: &ParmFixed,
+        k: i64,
+    ) -> ParmFixed {
+        ParmFixed {
+            ct: comparison::cmp_const_impl(
+                pc.params,
+                pc.pub_keys,
+                op,
+                &x.ct,
+                fixed_point::const_at_scale(k, x.scale).expect("ParmArithmetics::cmp_const failed."),
+            ).expect("ParmArithmetics::cmp_const failed."),
+            scale: 0,
+        }
+    }
+
+    fn select(
+        pc: &ParmesanCloudovo,
+        c: &ParmFixed,
+        x: &ParmFixed,
+        y: &ParmFixed,
+    ) -> ParmFixed {
+        // the condition must be a bit (e.g., result of comparison has scale 0)
+        let c_bit = Self::rescale(pc, c, 0);
+        let (xy, scale) = fixed_point::align_impl(pc.params, pc.pub_keys, &[x, y]).expect("ParmArithmetics::select failed.");
+        ParmFixed {
+            ct: selection::select_impl(
+                pc.params,
+                pc.pub_keys,
+                &c_bit.ct,
+                &xy[0],
+                &xy[1],
+            ).expect("ParmArithmetics::select failed."),
+            scale,
+        }
+    }
+}
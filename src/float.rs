@@ -0,0 +1,64 @@
+use crate::ciphertexts::ParmFloat;
+use crate::ParmesanCloudovo;
+use crate::cloudovo::*;
+
+
+// =============================================================================
+//
+//  Parmesan Floating-Point Arithmetics
+//
+
+/// Parmesan Floating-Point Arithmetics Trait
+/// * implemented for `ParmFloat`, and for `f64` as a reference (for accuracy comparisons)
+pub trait ParmFloatArithmetics {
+    /// Opposite: `-X`
+    fn opp(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+    ) -> Self;
+
+    /// Multiplication: `X × Y` (normalized)
+    fn mul(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+}
+
+impl ParmFloatArithmetics for f64 {
+    fn opp(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+    ) -> f64 {-x}
+
+    fn mul(
+        _pc: &ParmesanCloudovo,
+        x: &f64,
+        y: &f64,
+    ) -> f64 {x * y}
+}
+
+impl ParmFloatArithmetics for ParmFloat {
+    fn opp(
+        _pc: &ParmesanCloudovo,
+        x: &ParmFloat,
+    ) -> ParmFloat {
+        ParmFloat {
+            mant: addition::opposite_impl(&x.mant).expect("ParmFloatArithmetics::opp failed."),
+            exp: x.exp.clone(),
+        }
+    }
+
+    fn mul(
+        pc: &ParmesanCloudovo,
+        x: &ParmFloat,
+        y: &ParmFloat,
+    ) -> ParmFloat {
+        floating_point::mul_impl(
+            pc.params,
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmFloatArithmetics::mul failed.")
+    }
+}
@@ -23,6 +23,7 @@
 
 
 use std::error::Error;
+use std::sync::Arc;
 
 //TODO check whether needed
 pub use std::fs::{self,File,OpenOptions};
@@ -56,19 +57,28 @@ pub mod misc;
 pub mod params;
 pub use params::Params;
 pub mod ciphertexts;
-pub use ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+pub use ciphertexts::{ParmCiphertext, ParmCiphertextExt, ParmBool, ParmFixed, ParmFloat, EncInt};
 pub mod arithmetics;
-pub use arithmetics::ParmArithmetics;
+pub use arithmetics::{ParmArithmetics, ParmWordArithmetics};
+pub mod logic;
+pub use logic::ParmLogic;
+pub mod fixed;
+pub use fixed::ParmFixedArithmetics;
+pub mod float;
+pub use float::ParmFloatArithmetics;
 
 // Userovo modules
 pub mod userovo;
 pub use userovo::*;
-pub use userovo::keys::{PrivKeySet,PubKeySet};
+pub use userovo::keys::{PrivKeySet,PubKeySet,PubKeyBundle,KeyStore};
 
 // Cloudovo modules
 pub mod cloudovo;
 pub use cloudovo::*;
 pub use cloudovo::neural_network::{Perceptron, PercType, NeuralNetwork};
+pub use cloudovo::pbs::Lut;
+pub use cloudovo::comparison::CmpOp;
+pub use cloudovo::shifting::RoundMode;
 
 
 // =============================================================================
@@ -88,7 +98,7 @@ pub struct ParmesanUserovo<'a> {
 impl ParmesanUserovo<'_> {
     /// Create an instance of `ParmesanUserovo`
     /// * save immutable reference to params
-    /// * generate keys
+    /// * load or generate keys (in the default key store)
     pub fn new(params: &Params) -> Result<ParmesanUserovo, Box<dyn Error>> {
         Ok(ParmesanUserovo {
             params,
@@ -96,6 +106,14 @@ impl ParmesanUserovo<'_> {
         })
     }
 
+    /// Create an instance of `ParmesanUserovo` with given keys (e.g., loaded from a `KeyStore`)
+    pub fn with_priv_keys(params: &Params, priv_keys: PrivKeySet) -> ParmesanUserovo {
+        ParmesanUserovo {
+            params,
+            priv_keys,
+        }
+    }
+
     /// Get the Public Key Set
     pub fn export_pub_keys(&self) -> PubKeySet {
         PubKeySet {
@@ -105,6 +123,11 @@ impl ParmesanUserovo<'_> {
         }
     }
 
+    /// Get an owned copy of public keys, which can be saved & sent to the cloud
+    pub fn export_pub_key_bundle(&self) -> PubKeyBundle {
+        PubKeyBundle::new(self.params, &self.export_pub_keys())
+    }
+
     /// Encrypt a 64-bit signed integer
     /// * `bits` states how many bits of input `m` are to be encrypted, since this will be public
     /// * least significant bits, including sign, are taken
@@ -128,12 +151,71 @@ impl ParmesanUserovo<'_> {
     pub fn decrypt(&self, c: &ParmCiphertext) -> Result<i64, Box<dyn Error>> {   //WISH change to a template for other integer types/lengths, too
         Ok(encryption::parm_decrypt(self.params, &self.priv_keys, c)?)
     }
+
+    /// Decrypt ciphertext into a 128-bit signed integer
+    pub fn decrypt_i128(&self, c: &ParmCiphertext) -> Result<i128, Box<dyn Error>> {
+        Ok(encryption::parm_decrypt_i128(self.params, &self.priv_keys, c)?)
+    }
+
+    /// Encrypt a bit
+    pub fn encrypt_bool(
+        &self,
+        b: bool,
+    ) -> Result<ParmBool, Box<dyn Error>> {
+        Ok(encryption::parm_encrypt_bool(self.params, &self.priv_keys, b)?)
+    }
+
+    /// Decrypt a bit
+    pub fn decrypt_bool(&self, c: &ParmBool) -> Result<bool, Box<dyn Error>> {
+        Ok(encryption::parm_decrypt_bool(self.params, &self.priv_keys, c)?)
+    }
+
+    /// Encrypt a fixed-point number, rounded to `scale` fractional bits (`words` in total)
+    pub fn encrypt_fixed(
+        &self,
+        m: f64,
+        words: usize,
+        scale: usize,
+    ) -> Result<ParmFixed, Box<dyn Error>> {
+        Ok(encryption::parm_encrypt_fixed(self.params, &self.priv_keys, m, words, scale)?)
+    }
+
+    /// Decrypt a fixed-point number
+    pub fn decrypt_fixed(&self, c: &ParmFixed) -> Result<f64, Box<dyn Error>> {
+        Ok(encryption::parm_decrypt_fixed(self.params, &self.priv_keys, c)?)
+    }
+
+    /// Encrypt a floating-point number with `mant_words`-word mantissa & `exp_words`-word exponent
+    pub fn encrypt_float(
+        &self,
+        m: f64,
+        mant_words: usize,
+        exp_words: usize,
+    ) -> Result<ParmFloat, Box<dyn Error>> {
+        Ok(encryption::parm_encrypt_float(self.params, &self.priv_keys, m, mant_words, exp_words)?)
+    }
+
+    /// Decrypt a floating-point number
+    pub fn decrypt_float(&self, c: &ParmFloat) -> Result<f64, Box<dyn Error>> {
+        Ok(encryption::parm_decrypt_float(self.params, &self.priv_keys, c)?)
+    }
+
+    /// Decrypt ciphertext in canonical two's complement (i.e., bits in `{0,1}` with the last one being the sign bit)
+    pub fn decrypt_canonical(&self, c: &ParmCiphertext) -> Result<i64, Box<dyn Error>> {
+        if c.len() > 63 {
+            return Err(format!("Canonical ciphertext of length {} does not fit i64.", c.len()).into());
+        }
+        let m = self.decrypt(c)?;
+        // sign bit has weight -2^(len-1) instead of 2^(len-1) (n.b., 2^len overflows i64 for len = 63)
+        Ok(if c.len() > 0 && m >= 1 << (c.len() - 1) {(m as i128 - (1i128 << c.len())) as i64} else {m})
+    }
 }
 
 // -----------------------------------------------------------------------------
 //  Cloudovo
 
 /// # Cloud-side Parmesan
+#[derive(Clone, Copy)]
 pub struct ParmesanCloudovo<'a> {
     pub params: &'a Params,
     pub pub_keys: &'a PubKeySet<'a>,
@@ -153,6 +235,48 @@ impl ParmesanCloudovo<'_> {
     }
 }
 
+/// # Cloud-side Parmesan (owned)
+/// * owns params & public keys (behind `Arc`), hence it is `Send + Sync + 'static`
+///   and can be stored in long-lived structs, or cheaply cloned across threads
+/// * lend `ParmesanCloudovo` via `with_cloudovo()` to evaluate
+#[derive(Clone)]
+pub struct ParmesanCloudovoShared {
+    pub params: Arc<Params>,
+    pub pub_keys: Arc<PubKeyBundle>,
+}
+
+impl ParmesanCloudovoShared {
+
+    /// Create an instance of `ParmesanCloudovoShared`
+    /// * fails if the public key bundle was not generated for `params`
+    pub fn new(
+        params: Params,
+        pub_keys: PubKeyBundle,
+    ) -> Result<ParmesanCloudovoShared, Box<dyn Error>> {
+        // check params fingerprint
+        pub_keys.pub_keys(&params)?;
+
+        Ok(ParmesanCloudovoShared {
+            params: Arc::new(params),
+            pub_keys: Arc::new(pub_keys),
+        })
+    }
+
+    /// Evaluate `f` with a (borrowed) `ParmesanCloudovo`
+    /// * `PubKeySet` only references keys of the bundle, it lives for the call of `f`
+    pub fn with_cloudovo<R, F: FnOnce(&ParmesanCloudovo) -> R>(
+        &self,
+        f: F,
+    ) -> R {
+        let pub_keys = PubKeySet {
+            bsk:     &self.pub_keys.bsk,
+            ksk:     &self.pub_keys.ksk,
+            encoder: &self.pub_keys.encoder,
+        };
+        f(&ParmesanCloudovo::new(&self.params, &pub_keys))
+    }
+}
+
 
 // =============================================================================
 //
@@ -186,6 +310,14 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     const DEMO_BITLEN: usize =  28;
     const DEMO_N_MSGS: usize =   3;
     const DEMO_ADC:    i64   = -20;
+    const DEMO_DVC:    i64   =  37;
+    const DEMO_SHC:    usize =   5;
+    const DEMO_SHV:    i64   =   3;
+    const DEMO_WRW:    usize =  12;
+    const DEMO_SAT:    (i64, i64) = (-1000, 1000);
+    const DEMO_MCM:    [i64; 4] = [-121, 683, 2731, -10923];
+    const DEMO_POW:    u32   =   5;
+    const DEMO_POLY:   [i64; 6] = [7, -3, 0, 2, -1, 5];
 
     // ---------------------------------
     //  Cloudovo Scope
@@ -219,6 +351,8 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     let m_x32: i64 =  0b01100110010010111011011001100110;   // 1716237926
     let m_y32: i64 =  0b01001011100111010100110001010100;   // 1268599892   ->  2177219247569903992 which fits 63 bits (i64)
                                                             //                  AND it does not happen that some 1 would be at such a position due to redundant repre
+    let m_x48: i64 =  0b101101000111010110010101110001001011100100110101;   // 198417116870965
+    let m_y48: i64 = -0b100111010110001011100011110101010011010100011110;   // -173048054756638 ->  product needs 96 bits (i128)
 
     // encrypt all values
     let mut c: [ParmCiphertext; DEMO_N_MSGS] = [
@@ -242,6 +376,8 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     let cy17= pu.encrypt(m_y17, 17)?;
     let cx32= pu.encrypt(m_x32, 32)?;
     let cy32= pu.encrypt(m_y32, 32)?;
+    let cx48= pu.encrypt(m_x48, 48)?;
+    let cy48= pu.encrypt(m_y48, 48)?;
 
     // print message
     let mut intro_text = format!("{} messages ({} bits taken)", String::from("User:").bold().yellow(), DEMO_BITLEN);
@@ -272,7 +408,7 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
             //~ ["Programmable bootstrapping"],
             //~ [
                 //~ // positive identity is defined for any pi
-                //~ let _c = pbs::pos_id(&pc.pub_keys, &c[0][0])?;
+                //~ let _c = pbs::pos_id(pc.pub_keys, &c[0][0])?;
             //~ ]
         //~ );
     //~ }                                                  .
@@ -299,8 +435,25 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     let c_add  = ParmArithmetics::add(&pc, &c[0], &c[1]);
     let c_sub  = ParmArithmetics::sub(&pc, &c[1], &c[0]);
     let c_adc  = ParmArithmetics::add_const(&pc,  &c[0], DEMO_ADC);
+    let c_sum  = ParmArithmetics::sum(&pc, &c);
     let c_sgn  = ParmArithmetics::sgn(&pc, &c[2]       );
     let c_max  = ParmArithmetics::max(&pc, &c[1], &c[0]);
+    let c_lt   = ParmArithmetics::lt(&pc, &c[1], &c[0]);
+    let c_eqc  = ParmArithmetics::cmp_const(&pc, CmpOp::EQ, &cx4, m_x4);
+    let c_sel  = ParmArithmetics::select(&pc, &c_lt, &c[0], &c[2]);
+    let (c_div, c_rem) = ParmArithmetics::div_rem(&pc, &c[0], &cx8);
+    let (c_dvc, c_rmc) = ParmArithmetics::div_rem_const(&pc, &c[0], DEMO_DVC);
+    let c_shrc = ParmArithmetics::shr_const(&pc, &c[0], DEMO_SHC, RoundMode::ROUND);
+    let c_shv  = pu.encrypt(DEMO_SHV, 2)?;
+    let c_shl  = ParmArithmetics::shl(&pc, &cx8, &c_shv);
+    let c_shr  = ParmArithmetics::shr(&pc, &c[0], &c_shv, RoundMode::FLOOR);
+    let c_can  = conversion::to_canonical_impl(pc.params, pc.pub_keys, &c[2])?;
+    let c_rdn  = conversion::from_canonical_impl(&c_can)?;
+    let c_band = ParmWordArithmetics::bit_and(&pc, &c[0], &cx8);
+    let c_bxor = ParmWordArithmetics::bit_xor(&pc, &c[2], &cx8);
+    let c_wrap = ParmWordArithmetics::truncate_wrap(&pc, &c[1], DEMO_WRW);
+    let c_sat  = ParmArithmetics::saturate(&pc, &c[1], DEMO_SAT.0, DEMO_SAT.1);
+    let c_clw  = ParmWordArithmetics::clamp_to_words(&pc, &c[1], DEMO_WRW);
 
     let c_xy1  = ParmArithmetics::mul(&pc, &cx1,  &cy1 );
     let c_xy4  = ParmArithmetics::mul(&pc, &cx4,  &cy4 );
@@ -308,16 +461,26 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     let c_xy16 = ParmArithmetics::mul(&pc, &cx16, &cy16);
     let c_xy17 = ParmArithmetics::mul(&pc, &cx17, &cy17);
     let c_xy32 = ParmArithmetics::mul(&pc, &cx32, &cy32);
+    let c_xy48 = ParmArithmetics::mul(&pc, &cx48, &cy48);
+    let c_x32x8  = ParmArithmetics::mul(&pc, &cx32, &cx8 );
+    let c_x48y16 = ParmArithmetics::mul(&pc, &cx48, &cy16);
 
     let c_xx4  = ParmArithmetics::squ(&pc, &cx4 );
     let c_xx8  = ParmArithmetics::squ(&pc, &cx8 );
     let c_xx16 = ParmArithmetics::squ(&pc, &cx16);
     let c_xx17 = ParmArithmetics::squ(&pc, &cx17);
     let c_xx32 = ParmArithmetics::squ(&pc, &cx32);
+    let c_xx48 = ParmArithmetics::squ(&pc, &cx48);
+    let c_powx8  = ParmArithmetics::pow_const(&pc, &cx8, DEMO_POW);
+    let c_polyx8 = ParmArithmetics::poly_eval(&pc, &cx8, &DEMO_POLY);
+    let c_sqrtx16 = ParmArithmetics::isqrt(&pc, &cx16);
+    let c_sqrtxx16 = ParmArithmetics::isqrt(&pc, &c_xx16);
 
     let c_n121x16 = ParmArithmetics::scalar_mul(&pc, -121, &cx16);
     let c_n128x16 = ParmArithmetics::scalar_mul(&pc, -128, &cx16);
     let c_p3x16   = ParmArithmetics::scalar_mul(&pc,    3, &cx16);
+    let c_bigx16  = ParmArithmetics::scalar_mul(&pc, 3_000_000_019, &cx16);
+    let c_mcmx16  = ParmArithmetics::scalar_mul_many(&pc, &cx16, &DEMO_MCM);
 
 
     // =================================
@@ -326,8 +489,26 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     let m_add  = pu.decrypt(&c_add )?;
     let m_sub  = pu.decrypt(&c_sub )?;
     let m_adc  = pu.decrypt(&c_adc )?;
+    let m_sum  = pu.decrypt(&c_sum )?;
     let m_sgn  = pu.decrypt(&c_sgn )?;
     let m_max  = pu.decrypt(&c_max )?;
+    let m_lt   = pu.decrypt(&c_lt  )?;
+    let m_eqc  = pu.decrypt(&c_eqc )?;
+    let m_sel  = pu.decrypt(&c_sel )?;
+    let m_div  = pu.decrypt(&c_div )?;
+    let m_rem  = pu.decrypt(&c_rem )?;
+    let m_dvc  = pu.decrypt(&c_dvc )?;
+    let m_rmc  = pu.decrypt(&c_rmc )?;
+    let m_shrc = pu.decrypt(&c_shrc)?;
+    let m_shl  = pu.decrypt(&c_shl )?;
+    let m_shr  = pu.decrypt(&c_shr )?;
+    let m_can  = pu.decrypt_canonical(&c_can)?;
+    let m_rdn  = pu.decrypt(&c_rdn )?;
+    let m_band = pu.decrypt(&c_band)?;
+    let m_bxor = pu.decrypt(&c_bxor)?;
+    let m_wrap = pu.decrypt(&c_wrap)?;
+    let m_sat  = pu.decrypt(&c_sat )?;
+    let m_clw  = pu.decrypt(&c_clw )?;
 
     let m_xy1  = pu.decrypt(&c_xy1 )?;
     let m_xy4  = pu.decrypt(&c_xy4 )?;
@@ -335,16 +516,29 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
     let m_xy16 = pu.decrypt(&c_xy16)?;
     let m_xy17 = pu.decrypt(&c_xy17)?;
     let m_xy32 = pu.decrypt(&c_xy32)?;
+    let m_xy48 = pu.decrypt_i128(&c_xy48)?;
+    let m_x32x8  = pu.decrypt(&c_x32x8)?;
+    let m_x48y16 = pu.decrypt_i128(&c_x48y16)?;
 
     let m_xx4  = pu.decrypt(&c_xx4 )?;
     let m_xx8  = pu.decrypt(&c_xx8 )?;
     let m_xx16 = pu.decrypt(&c_xx16)?;
     let m_xx17 = pu.decrypt(&c_xx17)?;
     let m_xx32 = pu.decrypt(&c_xx32)?;
+    let m_xx48 = pu.decrypt_i128(&c_xx48)?;
+    let m_powx8  = pu.decrypt(&c_powx8 )?;
+    let m_polyx8 = pu.decrypt(&c_polyx8)?;
+    let m_sqrtx16  = pu.decrypt(&c_sqrtx16 )?;
+    let m_sqrtxx16 = pu.decrypt(&c_sqrtxx16)?;
 
     let m_n121x16 = pu.decrypt(&c_n121x16)?;
     let m_n128x16 = pu.decrypt(&c_n128x16)?;
     let m_p3x16   = pu.decrypt(&c_p3x16  )?;
+    let m_bigx16  = pu.decrypt(&c_bigx16 )?;
+    let mut m_mcmx16: Vec<i64> = Vec::new();
+    for ci in c_mcmx16.iter() {
+        m_mcmx16.push(pu.decrypt(ci)?);
+    }
 
     let mut summary_text = format!("{} results", String::from("User:").bold().yellow());
 
@@ -363,6 +557,11 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
                             if m_as[0] + (DEMO_ADC as i64) == m_adc {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
                             m_as[0] + (DEMO_ADC as i64)
     );
+    summary_text = format!("{}\nΣ m_i         = {:12} :: {} (exp. {})", summary_text,
+                            m_sum,
+                            if m_as.iter().sum::<i64>() == m_sum {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as.iter().sum::<i64>()
+    );
     summary_text = format!("{}\nsgn(m_2)      = {:12} :: {}", summary_text,
                             m_sgn,
                             if m_as[2].signum() == m_sgn {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
@@ -372,6 +571,91 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
                             if std::cmp::max(m_as[1], m_as[0]) == m_max {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
                             std::cmp::max(m_as[1], m_as[0])
     );
+    summary_text = format!("{}\nm_1 < m_0     = {:12} :: {} (exp. {})", summary_text,
+                            m_lt,
+                            if (m_as[1] < m_as[0]) as i64 == m_lt {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            (m_as[1] < m_as[0]) as i64
+    );
+    summary_text = format!("{}\nx_4 = {:3}     = {:12} :: {} (exp. {})", summary_text,
+                            m_x4, m_eqc,
+                            if 1 == m_eqc {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            1
+    );
+    summary_text = format!("{}\nm_1<m_0?m_0:m_2 = {:10} :: {} (exp. {})", summary_text,
+                            m_sel,
+                            if (if m_as[1] < m_as[0] {m_as[0]} else {m_as[2]}) == m_sel {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            if m_as[1] < m_as[0] {m_as[0]} else {m_as[2]}
+    );
+    summary_text = format!("{}\nm_0 / x_8     = {:12} :: {} (exp. {})", summary_text,
+                            m_div,
+                            if ParmArithmetics::div(&pc, &m_as[0], &m_x8) == m_div {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::div(&pc, &m_as[0], &m_x8)
+    );
+    summary_text = format!("{}\nm_0 % x_8     = {:12} :: {} (exp. {})", summary_text,
+                            m_rem,
+                            if ParmArithmetics::rem(&pc, &m_as[0], &m_x8) == m_rem {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::rem(&pc, &m_as[0], &m_x8)
+    );
+    summary_text = format!("{}\nm_0 div {:3}   = {:12} :: {} (exp. {})", summary_text,
+                            DEMO_DVC, m_dvc,
+                            if m_as[0].div_euclid(DEMO_DVC) == m_dvc {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[0].div_euclid(DEMO_DVC)
+    );
+    summary_text = format!("{}\nm_0 mod {:3}   = {:12} :: {} (exp. {})", summary_text,
+                            DEMO_DVC, m_rmc,
+                            if m_as[0].rem_euclid(DEMO_DVC) == m_rmc {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[0].rem_euclid(DEMO_DVC)
+    );
+    summary_text = format!("{}\nm_0 >> {} (rnd) = {:10} :: {} (exp. {})", summary_text,
+                            DEMO_SHC, m_shrc,
+                            if ParmArithmetics::shr_const(&pc, &m_as[0], DEMO_SHC, RoundMode::ROUND) == m_shrc {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::shr_const(&pc, &m_as[0], DEMO_SHC, RoundMode::ROUND)
+    );
+    summary_text = format!("{}\nx_8 << [{}]    = {:12} :: {} (exp. {})", summary_text,
+                            DEMO_SHV, m_shl,
+                            if m_x8 << DEMO_SHV == m_shl {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_x8 << DEMO_SHV
+    );
+    summary_text = format!("{}\nm_0 >> [{}]    = {:12} :: {} (exp. {})", summary_text,
+                            DEMO_SHV, m_shr,
+                            if m_as[0] >> DEMO_SHV == m_shr {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[0] >> DEMO_SHV
+    );
+    summary_text = format!("{}\ncanon(m_2)    = {:12} :: {} (exp. {})", summary_text,
+                            m_can,
+                            if m_as[2] == m_can {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[2]
+    );
+    summary_text = format!("{}\nredun(canon(m_2)) = {:8} :: {} (exp. {})", summary_text,
+                            m_rdn,
+                            if m_as[2] == m_rdn {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[2]
+    );
+    summary_text = format!("{}\nm_0 & x_8     = {:12} :: {} (exp. {})", summary_text,
+                            m_band,
+                            if m_as[0] & m_x8 == m_band {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[0] & m_x8
+    );
+    summary_text = format!("{}\nm_2 ^ x_8     = {:12} :: {} (exp. {})", summary_text,
+                            m_bxor,
+                            if m_as[2] ^ m_x8 == m_bxor {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_as[2] ^ m_x8
+    );
+    summary_text = format!("{}\nwrap_{}(m_1)   = {:12} :: {} (exp. {}, {} words)", summary_text,
+                            DEMO_WRW, m_wrap,
+                            if ParmWordArithmetics::truncate_wrap(&pc, &m_as[1], DEMO_WRW) == m_wrap {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmWordArithmetics::truncate_wrap(&pc, &m_as[1], DEMO_WRW), c_wrap.len()
+    );
+    summary_text = format!("{}\nsat(m_1)      = {:12} :: {} (exp. {})", summary_text,
+                            m_sat,
+                            if ParmArithmetics::saturate(&pc, &m_as[1], DEMO_SAT.0, DEMO_SAT.1) == m_sat {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::saturate(&pc, &m_as[1], DEMO_SAT.0, DEMO_SAT.1)
+    );
+    summary_text = format!("{}\nclamp_{}(m_1)  = {:12} :: {} (exp. {}, {} words)", summary_text,
+                            DEMO_WRW, m_clw,
+                            if ParmWordArithmetics::clamp_to_words(&pc, &m_as[1], DEMO_WRW) == m_clw {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmWordArithmetics::clamp_to_words(&pc, &m_as[1], DEMO_WRW), c_clw.len()
+    );
 
     summary_text = format!("{}\nx_1 × y_1     = {:12} :: {} (exp. {})", summary_text,
                             m_xy1,
@@ -403,6 +687,21 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
                             if m_x32 * m_y32 == m_xy32 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
                             m_x32 * m_y32
     );
+    summary_text = format!("{}\nx_48 × y_48   = {:30} :: {} (exp. {})", summary_text,
+                            m_xy48,
+                            if (m_x48 as i128) * (m_y48 as i128) == m_xy48 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            (m_x48 as i128) * (m_y48 as i128)
+    );
+    summary_text = format!("{}\nx_32 × x_8    = {:24} :: {} (exp. {})", summary_text,
+                            m_x32x8,
+                            if m_x32 * m_x8 == m_x32x8 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_x32 * m_x8
+    );
+    summary_text = format!("{}\nx_48 × y_16   = {:30} :: {} (exp. {})", summary_text,
+                            m_x48y16,
+                            if (m_x48 as i128) * (m_y16 as i128) == m_x48y16 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            (m_x48 as i128) * (m_y16 as i128)
+    );
 
     summary_text = format!("{}\nx_4  ^ 2      = {:12} :: {} (exp. {})", summary_text,
                             m_xx4,
@@ -429,6 +728,31 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
                             if m_xx32 == m_x32 * m_x32 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
                             m_x32 * m_x32
     );
+    summary_text = format!("{}\nx_48 ^ 2      = {:30} :: {} (exp. {})", summary_text,
+                            m_xx48,
+                            if m_xx48 == (m_x48 as i128) * (m_x48 as i128) {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            (m_x48 as i128) * (m_x48 as i128)
+    );
+    summary_text = format!("{}\nx_8  ^ {}      = {:12} :: {} (exp. {})", summary_text,
+                            DEMO_POW, m_powx8,
+                            if ParmArithmetics::pow_const(&pc, &m_x8, DEMO_POW) == m_powx8 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::pow_const(&pc, &m_x8, DEMO_POW)
+    );
+    summary_text = format!("{}\n{:?} (x_8) = {:12} :: {} (exp. {})", summary_text,
+                            DEMO_POLY, m_polyx8,
+                            if ParmArithmetics::poly_eval(&pc, &m_x8, &DEMO_POLY) == m_polyx8 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::poly_eval(&pc, &m_x8, &DEMO_POLY)
+    );
+    summary_text = format!("{}\n√x_16         = {:12} :: {} (exp. {})", summary_text,
+                            m_sqrtx16,
+                            if ParmArithmetics::isqrt(&pc, &m_x16) == m_sqrtx16 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            ParmArithmetics::isqrt(&pc, &m_x16)
+    );
+    summary_text = format!("{}\n√(x_16 ^ 2)   = {:12} :: {} (exp. {})", summary_text,
+                            m_sqrtxx16,
+                            if m_x16.abs() == m_sqrtxx16 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_x16.abs()
+    );
 
     summary_text = format!("{}\n-121 × x_16   = {:12} :: {} (exp. {})", summary_text,
                             m_n121x16,
@@ -445,6 +769,17 @@ pub fn arith_demo() -> Result<(), Box<dyn Error>> {
                             if 3 * m_x16 == m_p3x16 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
                             3 * m_x16
     );
+    summary_text = format!("{}\n3000000019 × x_16 = {:16} :: {} (exp. {})", summary_text,
+                            m_bigx16,
+                            if 3_000_000_019 * m_x16 == m_bigx16 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            3_000_000_019 * m_x16
+    );
+    let m_mcm_exp: Vec<i64> = DEMO_MCM.iter().map(|k| k * m_x16).collect();
+    summary_text = format!("{}\n{:?} × x_16 = {:?} :: {} (exp. {:?})", summary_text,
+                            DEMO_MCM, m_mcmx16,
+                            if m_mcm_exp == m_mcmx16 {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_mcm_exp
+    );
 
     infoln!("{}", summary_text);
 
@@ -567,7 +902,7 @@ pub fn nn_demo() -> Result<(), Box<dyn Error>> {
     //  U: Decryption
 
     let mut m_out_homo = Vec::new();
-    for ci in c_out {
+    for ci in &c_out {
         m_out_homo.push(pu.decrypt(&ci)?);
     }
 
@@ -586,6 +921,16 @@ pub fn nn_demo() -> Result<(), Box<dyn Error>> {
         );
     }
 
+    // label (argmax of output layer)
+    let c_label = ParmArithmetics::argmax(&pc, &c_out).0;
+    let m_label_homo = pu.decrypt(&c_label)?;
+    let m_label_plain = ParmArithmetics::argmax(&pc, &m_out_plain).0;
+    summary_text = format!("{}\nLABEL  = {:6} :: {} (exp. {})", summary_text,
+                            m_label_homo,
+                            if m_label_homo == m_label_plain {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                            m_label_plain
+    );
+
     infoln!("{}", summary_text);
 
 
@@ -596,6 +941,225 @@ pub fn nn_demo() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+
+// -----------------------------------------------------------------------------
+//  Logic Demo
+
+/// Check all logic gates against their truth tables, for all supported bit precisions
+/// * fails with the first bit precision where any row does not match (also for the `bool` reference)
+pub fn logic_demo() -> Result<(), Box<dyn Error>> {
+
+    infobox!("Logic Gates DEMO");
+
+    for par in &[&params::PARM90__PI_3__D_12__D, &params::PARM90__PI_4__D_12__E, &params::PARM90__PI_5__D_20__F, &params::PARM90__PI_7__D_74__I] {
+
+        // =================================
+        //  Initialization
+        let pu = ParmesanUserovo::new(par)?;
+        let pub_k = pu.export_pub_keys();
+        let pc = ParmesanCloudovo::new(par, &pub_k);
+
+        // =================================
+        //  U: Encryption
+        let bits = [false, true];
+        let mut cb: Vec<ParmBool> = Vec::new();
+        for b in bits.iter() {
+            cb.push(pu.encrypt_bool(*b)?);
+        }
+
+        let mut summary_text = format!("{} truth tables (pi = {})", String::from("User:").bold().yellow(), par.bit_precision);
+        let mut n_fail = 0usize;
+
+        // =================================
+        //  C: Evaluation & U: Decryption
+        //  (both encrypted gates & the bool reference are checked against truth tables)
+
+        // unary
+        for (x, cx) in bits.iter().zip(cb.iter()) {
+            let m_not = pu.decrypt_bool(&ParmLogic::not(&pc, cx))?;
+            let exp = !x;
+            let ok = m_not == exp && ParmLogic::not(&pc, x) == exp;
+            if !ok {n_fail += 1;}
+            summary_text = format!("{}\nNOT  {}       = {} :: {}", summary_text,
+                                    *x as u8, m_not as u8,
+                                    if ok {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+            );
+        }
+
+        // binary
+        let gates: [(&str, fn(&ParmesanCloudovo, &ParmBool, &ParmBool) -> ParmBool, fn(&ParmesanCloudovo, &bool, &bool) -> bool, fn(bool, bool) -> bool); 4] = [
+            ("AND ", ParmLogic::and,  ParmLogic::and,  |x, y|   x & y ),
+            ("OR  ", ParmLogic::or,   ParmLogic::or,   |x, y|   x | y ),
+            ("XOR ", ParmLogic::xor,  ParmLogic::xor,  |x, y|   x ^ y ),
+            ("NAND", ParmLogic::nand, ParmLogic::nand, |x, y| !(x & y)),
+        ];
+        for (name, gate_c, gate_m, table) in gates.iter() {
+            for (x, cx) in bits.iter().zip(cb.iter()) {
+                for (y, cy) in bits.iter().zip(cb.iter()) {
+                    let m_g = pu.decrypt_bool(&gate_c(&pc, cx, cy))?;
+                    let exp = table(*x, *y);
+                    let ok = m_g == exp && gate_m(&pc, x, y) == exp;
+                    if !ok {n_fail += 1;}
+                    summary_text = format!("{}\n{} {} {}     = {} :: {}", summary_text,
+                                            name, *x as u8, *y as u8, m_g as u8,
+                                            if ok {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                    );
+                }
+            }
+        }
+
+        // ternary
+        for (c, cc) in bits.iter().zip(cb.iter()) {
+            for (x, cx) in bits.iter().zip(cb.iter()) {
+                for (y, cy) in bits.iter().zip(cb.iter()) {
+                    let m_mux = pu.decrypt_bool(&ParmLogic::mux(&pc, cc, cx, cy))?;
+                    let exp = if *c {*x} else {*y};
+                    let ok = m_mux == exp && ParmLogic::mux(&pc, c, x, y) == exp;
+                    if !ok {n_fail += 1;}
+                    summary_text = format!("{}\nMUX  {} {} {}   = {} :: {}", summary_text,
+                                            *c as u8, *x as u8, *y as u8, m_mux as u8,
+                                            if ok {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                    );
+                }
+            }
+        }
+
+        summary_text = format!("{}\n{} failed", summary_text, n_fail);
+        infoln!("{}", summary_text);
+
+        if n_fail > 0 {
+            return Err(format!("Logic gates: {} truth-table rows failed (pi = {}).", n_fail, par.bit_precision).into());
+        }
+    }
+
+    // =================================
+    infobox!("Finished Logic Gates DEMO");
+    // =================================
+
+    Ok(())
+}
+
+/// Demo of fixed-point arithmetics, compared to `f64` reference
+pub fn fixed_demo() -> Result<(), Box<dyn Error>> {
+
+    infobox!("Fixed-Point DEMO");
+
+    const DEMO_FXP_WORDS: usize = 24;
+    const DEMO_FXP_SCALE: usize = 12;
+
+    // =================================
+    //  Initialization
+    let par = &params::PARM90__PI_5__D_20__F;
+    let pu = ParmesanUserovo::new(par)?;
+    let pub_k = pu.export_pub_keys();
+    let pc = ParmesanCloudovo::new(par, &pub_k);
+
+    // =================================
+    //  U: Encryption
+    let m_x: f64 =  3.25739046;
+    let m_y: f64 = -1.84616207;
+    let cx = pu.encrypt_fixed(m_x, DEMO_FXP_WORDS, DEMO_FXP_SCALE)?;
+    let cy = pu.encrypt_fixed(m_y, DEMO_FXP_WORDS, DEMO_FXP_SCALE)?;
+    // reference works with inputs rounded as encrypted
+    let r_x: f64 = ParmFixedArithmetics::rescale(&pc, &m_x, DEMO_FXP_SCALE);
+    let r_y: f64 = ParmFixedArithmetics::rescale(&pc, &m_y, DEMO_FXP_SCALE);
+
+    // =================================
+    //  C: Evaluation
+    let c_add = ParmArithmetics::add(&pc, &cx, &cy);
+    let c_sub = ParmArithmetics::sub(&pc, &cx, &cy);
+    let c_mul = ParmArithmetics::mul(&pc, &cx, &cy);
+    let c_sqx = ParmArithmetics::squ(&pc, &cx);
+    // generic code runs over ParmFixed, too
+    let c_nn  = demo_nn().eval(&pc, &vec![cx.clone(), cy.clone(), ParmArithmetics::zero()]);
+
+    // =================================
+    //  U: Decryption & comparison with f64 reference
+    let mut results = vec![
+        (String::from("x + y"), pu.decrypt_fixed(&c_add)?, ParmArithmetics::add(&pc, &r_x, &r_y)),
+        (String::from("x - y"), pu.decrypt_fixed(&c_sub)?, ParmArithmetics::sub(&pc, &r_x, &r_y)),
+        (String::from("x × y"), pu.decrypt_fixed(&c_mul)?, ParmArithmetics::mul(&pc, &r_x, &r_y)),
+        (String::from("x ^ 2"), pu.decrypt_fixed(&c_sqx)?, ParmArithmetics::squ(&pc, &r_x)),
+    ];
+    let r_nn: Vec<f64> = demo_nn().eval(&pc, &vec![r_x, r_y, 0.0]);
+    for (i, (c_nni, r_nni)) in c_nn.iter().zip(r_nn.iter()).enumerate() {
+        results.push((format!("NN_{} ", i), pu.decrypt_fixed(c_nni)?, *r_nni));
+    }
+
+    // rounding of the product introduces an error of at most half ulp
+    let tol = (-(DEMO_FXP_SCALE as f64)).exp2() / 2.0;
+    let mut summary_text = format!("{} results ({} words, scale {})", String::from("User:").bold().yellow(), DEMO_FXP_WORDS, DEMO_FXP_SCALE);
+    for (name, m, exp) in results.iter() {
+        summary_text = format!("{}\n{}         = {:14.8} :: {} (exp. {:.8}, err. {:.2e})", summary_text,
+                                name, m,
+                                if (m - exp).abs() <= tol {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                                exp, (m - exp).abs(),
+        );
+    }
+    infoln!("{}", summary_text);
+
+    // =================================
+    infobox!("Finished Fixed-Point DEMO");
+    // =================================
+
+    Ok(())
+}
+
+/// Demo of floating-point-like arithmetics: a chain of multiplications, compared to `f64` reference
+pub fn float_demo() -> Result<(), Box<dyn Error>> {
+
+    infobox!("Floating-Point DEMO");
+
+    const DEMO_FLP_MANT: usize = 16;
+    const DEMO_FLP_EXP:  usize = 10;
+    const DEMO_FLP_MULS: usize =  6;
+
+    // =================================
+    //  Initialization
+    let par = &params::PARM90__PI_5__D_20__F;
+    let pu = ParmesanUserovo::new(par)?;
+    let pub_k = pu.export_pub_keys();
+    let pc = ParmesanCloudovo::new(par, &pub_k);
+
+    // =================================
+    //  U: Encryption
+    let m_x: f64 = -5.43218907;
+    let m_y: f64 =  0.0271828183;
+    let cx = pu.encrypt_float(m_x, DEMO_FLP_MANT, DEMO_FLP_EXP)?;
+    let cy = pu.encrypt_float(m_y, DEMO_FLP_MANT, DEMO_FLP_EXP)?;
+    // reference works with inputs rounded as encrypted
+    let r_x = pu.decrypt_float(&cx)?;
+    let r_y = pu.decrypt_float(&cy)?;
+
+    // =================================
+    //  C: Evaluation & U: Decryption
+    //  alternately multiply by x & y, the length of the result does not grow
+    let mut summary_text = format!("{} results ({}-word mantissa, {}-word exponent)", String::from("User:").bold().yellow(), DEMO_FLP_MANT, DEMO_FLP_EXP);
+    let mut c_p = cx.clone();
+    let mut r_p = r_x;
+    for i in 1..DEMO_FLP_MULS+1 {
+        let (c_f, r_f) = if i % 2 == 1 {(&cy, r_y)} else {(&cx, r_x)};
+        c_p = ParmFloatArithmetics::mul(&pc, &c_p, c_f);
+        r_p = ParmFloatArithmetics::mul(&pc, &r_p, &r_f);
+
+        let m_p = pu.decrypt_float(&c_p)?;
+        // each rounding loses up to a few ulp's of the mantissa
+        let rel_err = ((m_p - r_p) / r_p).abs();
+        summary_text = format!("{}\n{}. product     = {:16.8e} :: {} (exp. {:.8e}, rel. err. {:.2e}, {}+{} words)", summary_text,
+                                i, m_p,
+                                if rel_err <= (i as f64) * (-((DEMO_FLP_MANT - 3) as f64)).exp2() {String::from("PASS").bold().green()} else {String::from("FAIL").bold().red()},
+                                r_p, rel_err, c_p.mant.len(), c_p.exp.len(),
+        );
+    }
+    infoln!("{}", summary_text);
+
+    // =================================
+    infobox!("Finished Floating-Point DEMO");
+    // =================================
+
+    Ok(())
+}
+
 /// Get a demo neural network
 ///
 /// * n.b., since Vec is an allocated structure, it cannot be in const/static.
@@ -0,0 +1,175 @@
+use crate::ciphertexts::ParmBool;
+use crate::ParmesanCloudovo;
+use crate::cloudovo::*;
+
+// parallelization tools
+use crossbeam_utils::thread;
+
+use concrete::LWE;
+
+
+// =============================================================================
+//
+//  Parmesan Logic
+//
+
+/// Parmesan Logic Trait (bits in `{0,1}`)
+pub trait ParmLogic {
+    /// Negation: `¬X`
+    fn not(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+    ) -> Self;
+
+    /// Conjunction: `X ∧ Y`
+    fn and(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Disjunction: `X ∨ Y`
+    fn or(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Exclusive or: `X ⊕ Y`
+    fn xor(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Negated conjunction: `¬(X ∧ Y)`
+    fn nand(
+        pc: &ParmesanCloudovo,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+
+    /// Multiplexer: `C ? X : Y`
+    fn mux(
+        pc: &ParmesanCloudovo,
+        c: &Self,
+        x: &Self,
+        y: &Self,
+    ) -> Self;
+}
+
+impl ParmLogic for bool {
+    fn not(
+        _pc: &ParmesanCloudovo,
+        x: &bool,
+    ) -> bool {!x}
+
+    fn and(
+        _pc: &ParmesanCloudovo,
+        x: &bool,
+        y: &bool,
+    ) -> bool {x & y}
+
+    fn or(
+        _pc: &ParmesanCloudovo,
+        x: &bool,
+        y: &bool,
+    ) -> bool {x | y}
+
+    fn xor(
+        _pc: &ParmesanCloudovo,
+        x: &bool,
+        y: &bool,
+    ) -> bool {x ^ y}
+
+    fn nand(
+        _pc: &ParmesanCloudovo,
+        x: &bool,
+        y: &bool,
+    ) -> bool {!(x & y)}
+
+    fn mux(
+        _pc: &ParmesanCloudovo,
+        c: &bool,
+        x: &bool,
+        y: &bool,
+    ) -> bool {if *c {*x} else {*y}}
+}
+
+impl ParmLogic for ParmBool {
+    fn not(
+        pc: &ParmesanCloudovo,
+        x: &ParmBool,
+    ) -> ParmBool {
+        // 1 - x, no bootstrapping
+        LWE::encrypt_uint_triv(1, pc.pub_keys.encoder)
+            .and_then(|one| one.sub_uint(x))
+            .expect("ParmLogic::not failed.")
+    }
+
+    fn and(
+        pc: &ParmesanCloudovo,
+        x: &ParmBool,
+        y: &ParmBool,
+    ) -> ParmBool {
+        pbs::AND(
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmLogic::and failed.")
+    }
+
+    fn or(
+        pc: &ParmesanCloudovo,
+        x: &ParmBool,
+        y: &ParmBool,
+    ) -> ParmBool {
+        pbs::OR(
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmLogic::or failed.")
+    }
+
+    fn xor(
+        pc: &ParmesanCloudovo,
+        x: &ParmBool,
+        y: &ParmBool,
+    ) -> ParmBool {
+        pbs::XOR(
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmLogic::xor failed.")
+    }
+
+    fn nand(
+        pc: &ParmesanCloudovo,
+        x: &ParmBool,
+        y: &ParmBool,
+    ) -> ParmBool {
+        pbs::NAND(
+            pc.pub_keys,
+            x,
+            y,
+        ).expect("ParmLogic::nand failed.")
+    }
+
+    fn mux(
+        pc: &ParmesanCloudovo,
+        c: &ParmBool,
+        x: &ParmBool,
+        y: &ParmBool,
+    ) -> ParmBool {
+        // (C ∧ X) + (Y ∧ ¬C), the terms are never both 1 (in parallel)
+        let mut cx = LWE::encrypt_uint_triv(0, pc.pub_keys.encoder).expect("LWE::encrypt_uint_triv failed.");
+        let mut yc = LWE::encrypt_uint_triv(0, pc.pub_keys.encoder).expect("LWE::encrypt_uint_triv failed.");
+        let (cxr, ycr) = (&mut cx, &mut yc);
+        thread::scope(|mux_scope| {
+            mux_scope.spawn(|_| {*cxr = pbs::AND(pc.pub_keys, c, x).expect("pbs::AND failed.");});
+            mux_scope.spawn(|_| {*ycr = pbs::AND_NOT(pc.pub_keys, y, c).expect("pbs::AND_NOT failed.");});
+        }).expect("thread::scope mux_scope failed.");
+
+        cx.add_uint(&yc).expect("ParmLogic::mux failed.")
+    }
+}
@@ -1,5 +1,9 @@
+use std::error::Error;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
 use concrete::*;
-//WISH use serde::{Deserialize, Serialize};
 
 /// # Parmesan Parameters
 /// Contains
@@ -7,7 +11,7 @@ use concrete::*;
 /// * plaintext precision
 /// * quadratic weights
 /// * THFE parameters
-#[derive(Clone, Debug)]   //WISH Serialize, Deserialize (also elsewhere)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Params {
     pub        maxlen:  usize,          // not used (btw, what was the intended purpose??)
     pub bit_precision:  usize,          // aka. pi
@@ -38,6 +42,65 @@ impl Params {
     pub fn plaintext_space_size(&self) -> i32 {
         1i32 << self.bit_precision
     }
+
+    /// Get a named parameter set from the registry, e.g., `Params::by_name("PARM112__PI_5__D_20")`
+    pub fn by_name(name: &str) -> Option<&'static Params> {
+        PARAMS_REGISTRY.iter()
+                       .find(|(n, _)| *n == name)
+                       .map(|(_, par)| *par)
+    }
+
+    /// List names of all parameter sets in the registry
+    pub fn list() -> Vec<&'static str> {
+        PARAMS_REGISTRY.iter().map(|(n, _)| *n).collect()
+    }
+
+    /// Get name of a registered parameter set that equals `self` (if any)
+    pub fn name(&self) -> Option<&'static str> {
+        PARAMS_REGISTRY.iter()
+                       .find(|(_, par)| *par == self)
+                       .map(|(n, _)| *n)
+    }
+
+    /// Get fingerprint of the parameter set (FNV-1a hash of its JSON serialization)
+    /// * used to bind keys & ciphertexts to the exact parameters
+    pub fn fingerprint(&self) -> u64 {
+        let json = serde_json::to_string(self).expect("serde_json::to_string failed.");
+        let mut h = 0xcbf2_9ce4_8422_2325u64;
+        for b in json.bytes() {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x0100_0000_01b3);
+        }
+        h
+    }
+
+    /// Serialize into JSON (on-disk format)
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserialize from JSON
+    /// * a registered name (as a JSON string, e.g., `"PARM80__PI_5__D_20"`) is accepted, too
+    pub fn from_json(json: &str) -> Result<Params, Box<dyn Error>> {
+        if let Ok(name) = serde_json::from_str::<String>(json) {
+            return match Params::by_name(name.as_str()) {
+                Some(par) => Ok(par.clone()),
+                None => Err(format!("Unknown parameter set \"{}\" (available: {}).", name, Params::list().join(", ")).into()),
+            };
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Save into a JSON file
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Load from a JSON file
+    pub fn load(path: &str) -> Result<Params, Box<dyn Error>> {
+        Params::from_json(fs::read_to_string(path)?.as_str())
+    }
 }
 
 #[allow(dead_code)]
@@ -533,3 +596,39 @@ pub const PARM90__PI_7__D_74__I: Params = Params {
     ks_base_log: 1,
        ks_level: 20,
 };
+
+
+// #############################################################################
+//
+//  Parameter Registry
+//
+
+/// Named parameter sets, looked up by `Params::by_name` and listed by `Params::list`
+/// * n.b., names equal to names of the constants above
+const PARAMS_REGISTRY: [(&str, &Params); 22] = [
+    ("PARMXX__TRIVIAL",         &PARMXX__TRIVIAL        ),
+    // current
+    ("PARM80__PI_5__D_20",      &PARM80__PI_5__D_20     ),
+    ("PARM112__PI_5__D_20",     &PARM112__PI_5__D_20    ),
+    ("PARM128__PI_5__D_20",     &PARM128__PI_5__D_20    ),
+    // deprecated, 112-bit
+    ("PARM112__PI_2__D_02__A",  &PARM112__PI_2__D_02__A ),
+    ("PARM112__PI_2__D_03__B",  &PARM112__PI_2__D_03__B ),
+    ("PARM112__PI_3__D_19__C",  &PARM112__PI_3__D_19__C ),
+    ("PARM112__PI_3__D_12__D",  &PARM112__PI_3__D_12__D ),
+    ("PARM112__PI_4__D_12__E",  &PARM112__PI_4__D_12__E ),
+    ("PARM112__PI_5__D_20__F",  &PARM112__PI_5__D_20__F ),
+    ("PARM112__PI_4__D_36__G",  &PARM112__PI_4__D_36__G ),
+    ("PARM112__PI_5__D_36__H",  &PARM112__PI_5__D_36__H ),
+    ("PARM112__PI_7__D_74__I",  &PARM112__PI_7__D_74__I ),
+    // deprecated, 90-bit
+    ("PARM90__PI_2__D_02__A",   &PARM90__PI_2__D_02__A  ),
+    ("PARM90__PI_2__D_03__B",   &PARM90__PI_2__D_03__B  ),
+    ("PARM90__PI_3__D_19__C",   &PARM90__PI_3__D_19__C  ),
+    ("PARM90__PI_3__D_12__D",   &PARM90__PI_3__D_12__D  ),
+    ("PARM90__PI_4__D_12__E",   &PARM90__PI_4__D_12__E  ),
+    ("PARM90__PI_5__D_20__F",   &PARM90__PI_5__D_20__F  ),
+    ("PARM90__PI_4__D_36__G",   &PARM90__PI_4__D_36__G  ),
+    ("PARM90__PI_5__D_36__H",   &PARM90__PI_5__D_36__H  ),
+    ("PARM90__PI_7__D_74__I",   &PARM90__PI_7__D_74__I  ),
+];
@@ -7,7 +7,7 @@ use concrete::LWE;
 
 use crate::params::Params;
 use crate::userovo::keys::PrivKeySet;
-use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt};
+use crate::ciphertexts::{ParmCiphertext, ParmCiphertextExt, ParmBool, ParmFixed, ParmFloat};
 
 
 
@@ -57,6 +57,68 @@ pub fn parm_encrypt_vec(
     Ok(res)
 }
 
+/// Parmesan encryption of a fixed-point number
+/// * `m` is rounded to `scale` fractional bits, `words` is the total length
+pub fn parm_encrypt_fixed(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    m: f64,
+    words: usize,
+    scale: usize,
+) -> Result<ParmFixed, Box<dyn Error>> {
+    let m_sc = (m * (scale as f64).exp2()).round();
+    if !m_sc.is_finite() || m_sc.abs() >= (std::cmp::min(words, 63) as f64).exp2() {
+        return Err(format!("Fixed-point number {} does not fit {} words with scale {}.", m, words, scale).into());
+    }
+
+    Ok(ParmFixed {
+        ct: parm_encrypt(params, priv_keys, m_sc as i64, words)?,
+        scale,
+    })
+}
+
+/// Parmesan encryption of a floating-point number
+/// * mantissa is normalized, i.e., `2^(mant_words-1) ≤ |mant| < 2^mant_words` (unless `m` is zero)
+pub fn parm_encrypt_float(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    m: f64,
+    mant_words: usize,
+    exp_words: usize,
+) -> Result<ParmFloat, Box<dyn Error>> {
+    if !m.is_finite() || mant_words == 0 || mant_words > 62 || exp_words == 0 || exp_words > 62 {
+        return Err(format!("Floating-point number {} with {}-word mantissa & {}-word exponent not supported.", m, mant_words, exp_words).into());
+    }
+
+    let (mut mant, mut exp) = (0i64, 0i64);
+    if m != 0.0 {
+        exp = (m.abs().log2().floor() as i64) - (mant_words as i64 - 1);
+        mant = (m / (exp as f64).exp2()).round() as i64;
+        // rounding up to 2^mant_words
+        if mant.abs() >= 1 << mant_words {
+            mant /= 2;
+            exp += 1;
+        }
+    }
+    if exp < -(1 << (exp_words - 1)) || exp >= 1 << (exp_words - 1) {
+        return Err(format!("Exponent {} does not fit {}-bit two's complement.", exp, exp_words).into());
+    }
+
+    Ok(ParmFloat {
+        mant: parm_encrypt(params, priv_keys, mant, mant_words)?,
+        exp:  parm_encrypt(params, priv_keys, exp,  exp_words)?,
+    })
+}
+
+/// Parmesan encryption of a bit (as a word in `{0,1}`)
+pub fn parm_encrypt_bool(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    b: bool,
+) -> Result<ParmBool, Box<dyn Error>> {
+    parm_encr_word(params, priv_keys, b as i32)
+}
+
 fn parm_encr_word(
     params: &Params,
     priv_keys: &PrivKeySet,
@@ -111,6 +173,66 @@ pub fn parm_decrypt(
     Ok(m)
 }
 
+/// Parmesan decryption of a bit
+pub fn parm_decrypt_bool(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    c: &ParmBool,
+) -> Result<bool, Box<dyn Error>> {
+    match parm_decr_word(params, priv_keys, c)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        mi => Err(format!("Bit out of alphabet {{0,1}}: {}.", mi).into()),
+    }
+}
+
+/// Parmesan decryption into a 128-bit signed integer (e.g., for products of long integers)
+pub fn parm_decrypt_i128(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    pc: &ParmCiphertext,
+) -> Result<i128, Box<dyn Error>> {
+    if pc.len() > 127 {
+        return Err(format!("Ciphertext of length {} does not fit i128.", pc.len()).into());
+    }
+
+    let mut m = 0i128;
+
+    for (i, ct) in pc.iter().enumerate() {
+        m += match parm_decr_word(params, priv_keys, ct)? {
+             1 => {  1i128 << i},
+             0 => {  0i128},
+            -1 => {-(1i128 << i)},
+            mi => {panic!("Word m_[{}] out of redundant bin alphabet: {}.", i, mi)},
+        };
+    }
+
+    Ok(m)
+}
+
+/// Parmesan decryption of a fixed-point number
+pub fn parm_decrypt_fixed(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    pf: &ParmFixed,
+) -> Result<f64, Box<dyn Error>> {
+    let m = parm_decrypt_i128(params, priv_keys, &pf.ct)?;
+
+    Ok((m as f64) / (pf.scale as f64).exp2())
+}
+
+/// Parmesan decryption of a floating-point number
+pub fn parm_decrypt_float(
+    params: &Params,
+    priv_keys: &PrivKeySet,
+    pf: &ParmFloat,
+) -> Result<f64, Box<dyn Error>> {
+    let mant = parm_decrypt_i128(params, priv_keys, &pf.mant)?;
+    let exp  = parm_decrypt(params, priv_keys, &pf.exp)?;
+
+    Ok((mant as f64) * (exp as f64).exp2())
+}
+
 fn parm_decr_word(
     params: &Params,
     priv_keys: &PrivKeySet,
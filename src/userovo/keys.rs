@@ -1,5 +1,5 @@
 use std::error::Error;
-use std::path::Path;
+use std::path::{Path,PathBuf};
 #[allow(unused_imports)]
 use std::io::{self,Write};
 //TODO add feature condition
@@ -10,11 +10,14 @@ pub use std::fs::{self,File,OpenOptions};
 #[allow(unused_imports)]
 use colored::Colorize;
 
+use serde::{Deserialize, Serialize};
+
 use concrete::*;
 
 use crate::*;
 use crate::params::Params;
 
+/// Default directory of the key store
 pub const KEYS_PATH: &str = "./keys/";
 
 
@@ -37,55 +40,9 @@ pub struct PrivKeySet {
 
 impl PrivKeySet {
 
-    /// Load or generate a TFHE key set
+    /// Load or generate a TFHE key set in the default key store (at `KEYS_PATH`)
     pub fn new(params: &Params) -> Result<PrivKeySet, Box<dyn Error>> {
-        // derive filenames
-        let (sk_file, bsk_file, ksk_file) = PrivKeySet::filenames_from_params(params);
-
-        // check if the keys exist
-        if     Path::new( sk_file.as_str()).is_file()
-            && Path::new(bsk_file.as_str()).is_file()
-            && Path::new(ksk_file.as_str()).is_file()
-        {
-            // load keys from files
-            measure_duration!(
-                ["Load PrivKeySet"],
-                [
-                    let keys = PrivKeySet {
-                         sk: LWESecretKey::load( sk_file.as_str())?,
-                        bsk:       LWEBSK::load(bsk_file.as_str()),     // does not return Result enum
-                        ksk:       LWEKSK::load(ksk_file.as_str()),     // does not return Result enum
-                        encoder:   PrivKeySet::get_encoder(params)?,
-                    };
-                ]
-            );
-
-            return Ok(keys);
-        } else {
-            // generate & save keys
-            measure_duration!(
-                ["Generate & Save PrivKeySet"],
-                [
-                    let keys = PrivKeySet::generate(params)?;
-                    measure_duration!(
-                        ["Creating empty keys directory"],
-                        [fs::create_dir_all(Path::new(sk_file.as_str()).parent().unwrap())?;
-                         fs::create_dir_all(Path::new(bsk_file.as_str()).parent().unwrap())?;
-                         fs::create_dir_all(Path::new(ksk_file.as_str()).parent().unwrap())?;]);
-                    measure_duration!(
-                        ["Saving  LWE secret key"],
-                        [keys .sk.save( sk_file.as_str())?;]);
-                    measure_duration!(
-                        ["Saving bootstrapping keys"],
-                        [keys.bsk.save(bsk_file.as_str());]);
-                    measure_duration!(
-                        ["Saving key-switching keys"],
-                        [keys.ksk.save(ksk_file.as_str());]);
-                ]
-            );
-
-            return Ok(keys);
-        }
+        KeyStore::new(KEYS_PATH).load_or_generate(params)
     }
 
     /// Generate a fresh TFHE key set
@@ -135,23 +92,6 @@ impl PrivKeySet {
             true,                                               // negacyclic?
         )?)
     }
-
-    /// Get filenames from params
-    fn filenames_from_params(par: &Params) -> (String, String, String) {
-        let suffix = format!("n-{}_N-{}_gamma-{}_l-{}_kappa-{}_t-{}.key",
-                                par.lwe_params.dimension,
-                                     par.rlwe_params.polynomial_size,
-                                              par.bs_base_log,
-                                                   par.bs_level,
-                                                            par.ks_base_log,
-                                                                 par.ks_level,
-        );
-        let  sk_file = format!( "{}/SK__{}", KEYS_PATH, suffix);
-        let  bk_file = format!( "{}/BK__{}", KEYS_PATH, suffix);
-        let ksk_file = format!("{}/KSK__{}", KEYS_PATH, suffix);
-
-        (sk_file, bk_file, ksk_file)
-    }
 }
 
 
@@ -161,9 +101,351 @@ impl PrivKeySet {
 //  Public Keys
 //
 
-//WISH #[derive(Serialize, Deserialize)]
+/// # Public Key Set
+/// * only references keys held elsewhere (e.g., in `PrivKeySet`, or in `PubKeyBundle`), hence it is cheap to copy
+#[derive(Clone, Copy)]
 pub struct PubKeySet<'a> {
     pub bsk:     &'a LWEBSK,
     pub ksk:     &'a LWEKSK,
     pub encoder: &'a Encoder,
 }
+
+/// # Public Key Bundle
+/// * owns the keys, hence it can be exported by the user (into a file or byte buffer)
+///   and imported by the cloud (in another process, or on another machine)
+/// * holds fingerprint of params the keys were generated for
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PubKeyBundle {
+    pub params_fp:  u64,
+    pub bsk:        LWEBSK,
+    pub ksk:        LWEKSK,
+    pub encoder:    Encoder,
+}
+
+impl PubKeyBundle {
+
+    /// Create a bundle from (copies of) public keys
+    pub fn new(
+        params: &Params,
+        pub_keys: &PubKeySet,
+    ) -> PubKeyBundle {
+        PubKeyBundle {
+            params_fp:  params.fingerprint(),
+            bsk:        pub_keys.bsk.clone(),
+            ksk:        pub_keys.ksk.clone(),
+            encoder:    pub_keys.encoder.clone(),
+        }
+    }
+
+    /// Get the Public Key Set (i.e., references to the keys in the bundle)
+    /// * fails if the bundle was not generated for `params`
+    pub fn pub_keys(&self, params: &Params) -> Result<PubKeySet, Box<dyn Error>> {
+        if self.params_fp != params.fingerprint() {
+            return Err(format!("Public key bundle was generated for different params (fingerprint {:016x}, expected {:016x}).",
+                                self.params_fp, params.fingerprint()).into());
+        }
+        Ok(PubKeySet {
+            bsk:     &self.bsk,
+            ksk:     &self.ksk,
+            encoder: &self.encoder,
+        })
+    }
+
+    /// Serialize into a byte buffer
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    /// Deserialize from a byte buffer
+    pub fn from_bytes(bytes: &[u8]) -> Result<PubKeyBundle, Box<dyn Error>> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    /// Save into a file
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        measure_duration!(
+            ["Saving public key bundle"],
+            [fs::write(path, self.to_bytes()?)?;]);
+        Ok(())
+    }
+
+    /// Load from a file
+    pub fn load(path: &str) -> Result<PubKeyBundle, Box<dyn Error>> {
+        measure_duration!(
+            ["Loading public key bundle"],
+            [let bundle = PubKeyBundle::from_bytes(&fs::read(path)?)?;]);
+        Ok(bundle)
+    }
+}
+
+
+
+// =============================================================================
+//
+//  Key Store
+//
+
+/// Manifest of a key set in the key store
+/// * binds key files to the exact params
+#[derive(Serialize, Deserialize)]
+struct KeyManifest {
+    params:     Params,
+    params_fp:  u64,
+    sk_file:    String,
+    bsk_file:   String,
+    ksk_file:   String,
+}
+
+/// # Key Store
+/// * directory with key sets (one per params), each described by its manifest
+/// * key sets of the legacy format (`*.key` files without manifest) are migrated, never overwritten
+pub struct KeyStore {
+    dir: PathBuf,
+}
+
+impl KeyStore {
+
+    /// Create a key store in given directory (created on first `generate`)
+    pub fn new<P: AsRef<Path>>(dir: P) -> KeyStore {
+        KeyStore {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Get directory of the key store
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Check whether a complete key set for `params` is present
+    pub fn exists(&self, params: &Params) -> bool {
+        let (sk_file, bsk_file, ksk_file, manifest_file) = self.paths_from_params(params);
+
+        manifest_file.is_file()
+            &&  sk_file.is_file()
+            && bsk_file.is_file()
+            && ksk_file.is_file()
+    }
+
+    /// Check whether a complete key set for `params` is present in the legacy format
+    pub fn exists_legacy(&self, params: &Params) -> bool {
+        self.legacy_paths_from_params(params).iter().all(|f| f.is_file())
+    }
+
+    /// Load a key set for `params`, or generate (and save) a new one if not present
+    /// * a legacy key set is migrated (cf. `migrate_legacy`)
+    /// * fails if an incomplete key set is found (no key file is ever overwritten)
+    pub fn load_or_generate(&self, params: &Params) -> Result<PrivKeySet, Box<dyn Error>> {
+        if self.exists(params) {
+            self.load(params)
+        } else if self.exists_legacy(params) {
+            self.migrate_legacy(params)
+        } else {
+            self.generate(params)
+        }
+    }
+
+    /// Load a key set for `params`
+    /// * fails if any of the files is missing or corrupted, or if the manifest does not match `params`
+    pub fn load(&self, params: &Params) -> Result<PrivKeySet, Box<dyn Error>> {
+        let (sk_file, bsk_file, ksk_file, manifest_file) = self.paths_from_params(params);
+
+        measure_duration!(
+            ["Load PrivKeySet"],
+            [
+                // check manifest
+                let manifest_json = fs::read_to_string(&manifest_file)
+                    .map_err(|e| format!("Cannot read key manifest {}: {}", manifest_file.display(), e))?;
+                let manifest: KeyManifest = serde_json::from_str(manifest_json.as_str())
+                    .map_err(|e| format!("Corrupted key manifest {}: {}", manifest_file.display(), e))?;
+                if manifest.params != *params || manifest.params_fp != params.fingerprint() {
+                    return Err(format!("Keys in {} were generated for different params.", manifest_file.display()).into());
+                }
+
+                // load keys
+                let keys = PrivKeySet {
+                     sk: KeyStore::read_key( &sk_file)?,
+                    bsk: KeyStore::read_key(&bsk_file)?,
+                    ksk: KeyStore::read_key(&ksk_file)?,
+                    encoder: PrivKeySet::get_encoder(params)?,
+                };
+            ]
+        );
+
+        Ok(keys)
+    }
+
+    /// Generate a fresh key set for `params` and save it
+    /// * fails if any key file for `params` is present (in either format), i.e., keys are never overwritten
+    pub fn generate(&self, params: &Params) -> Result<PrivKeySet, Box<dyn Error>> {
+        self.check_no_files(params)?;
+
+        measure_duration!(
+            ["Generate & Save PrivKeySet"],
+            [
+                let keys = PrivKeySet::generate(params)?;
+                self.save(params, &keys)?;
+            ]
+        );
+
+        Ok(keys)
+    }
+
+    /// Migrate a legacy key set for `params` (`*.key` files saved by `concrete`, without manifest)
+    /// * keys are saved in the current format, legacy files are left untouched
+    /// * fails if any key file of the current format is present, if any legacy file is corrupted,
+    ///   or if legacy keys do not match `params` (the legacy format does not hold params)
+    pub fn migrate_legacy(&self, params: &Params) -> Result<PrivKeySet, Box<dyn Error>> {
+        let (sk_file, bsk_file, ksk_file, manifest_file) = self.paths_from_params(params);
+        if [&sk_file, &bsk_file, &ksk_file, &manifest_file].iter().any(|f| f.exists()) {
+            return Err(format!("Incomplete key set in {}, refusing to overwrite it with migrated legacy keys.", self.dir.display()).into());
+        }
+
+        let legacy = self.legacy_paths_from_params(params);
+        let legacy_str: Vec<String> = legacy.iter().map(|f| f.to_string_lossy().into_owned()).collect();
+
+        measure_duration!(
+            ["Migrate legacy PrivKeySet"],
+            [
+                let keys = PrivKeySet {
+                     sk: LWESecretKey::load(legacy_str[0].as_str())
+                            .map_err(|e| format!("Corrupted legacy key file {}: {}", legacy[0].display(), e))?,
+                    bsk: KeyStore::load_legacy(&legacy[1], || LWEBSK::load(legacy_str[1].as_str()))?,
+                    ksk: KeyStore::load_legacy(&legacy[2], || LWEKSK::load(legacy_str[2].as_str()))?,
+                    encoder: PrivKeySet::get_encoder(params)?,
+                };
+                KeyStore::check_legacy_keys(params, &keys)?;
+                self.save(params, &keys)?;
+            ]
+        );
+
+        Ok(keys)
+    }
+
+    /// Load a legacy key file by a loader of `concrete`, which panics (instead of returning an error) on failure
+    fn load_legacy<K, F: FnOnce() -> K + std::panic::UnwindSafe>(
+        path: &Path,
+        load: F,
+    ) -> Result<K, Box<dyn Error>> {
+        std::panic::catch_unwind(load)
+            .map_err(|_| format!("Corrupted legacy key file {}.", path.display()).into())
+    }
+
+    /// Check dimensions & decomposition parameters of (legacy) keys against `params`
+    fn check_legacy_keys(params: &Params, keys: &PrivKeySet) -> Result<(), Box<dyn Error>> {
+        let rlwe_lwe_dim = params.rlwe_params.dimension * params.rlwe_params.polynomial_size;
+
+        if keys.sk.dimension != params.lwe_params.dimension {
+            return Err(format!("Legacy LWE secret key has dimension {}, expected {}.",
+                                keys.sk.dimension, params.lwe_params.dimension).into());
+        }
+        if keys.bsk.dimension       != params.rlwe_params.dimension
+        || keys.bsk.polynomial_size != params.rlwe_params.polynomial_size
+        || keys.bsk.base_log        != params.bs_base_log
+        || keys.bsk.level           != params.bs_level {
+            return Err(format!("Legacy bootstrapping keys (k = {}, N = {}, gamma = {}, l = {}) do not match params (k = {}, N = {}, gamma = {}, l = {}).",
+                                keys.bsk.dimension, keys.bsk.polynomial_size, keys.bsk.base_log, keys.bsk.level,
+                                params.rlwe_params.dimension, params.rlwe_params.polynomial_size, params.bs_base_log, params.bs_level).into());
+        }
+        if keys.ksk.dimension_before != rlwe_lwe_dim
+        || keys.ksk.dimension_after  != params.lwe_params.dimension
+        || keys.ksk.base_log         != params.ks_base_log
+        || keys.ksk.level            != params.ks_level {
+            return Err(format!("Legacy key-switching keys ({} -> {}, kappa = {}, t = {}) do not match params ({} -> {}, kappa = {}, t = {}).",
+                                keys.ksk.dimension_before, keys.ksk.dimension_after, keys.ksk.base_log, keys.ksk.level,
+                                rlwe_lwe_dim, params.lwe_params.dimension, params.ks_base_log, params.ks_level).into());
+        }
+
+        Ok(())
+    }
+
+    /// Save a key set for `params` (manifest goes last: it marks the key set as complete)
+    fn save(&self, params: &Params, keys: &PrivKeySet) -> Result<(), Box<dyn Error>> {
+        let (sk_file, bsk_file, ksk_file, manifest_file) = self.paths_from_params(params);
+
+        measure_duration!(
+            ["Creating keys directory"],
+            [fs::create_dir_all(&self.dir)?;]);
+        measure_duration!(
+            ["Saving  LWE secret key"],
+            [KeyStore::write_key( &sk_file, &keys.sk )?;]);
+        measure_duration!(
+            ["Saving bootstrapping keys"],
+            [KeyStore::write_key(&bsk_file, &keys.bsk)?;]);
+        measure_duration!(
+            ["Saving key-switching keys"],
+            [KeyStore::write_key(&ksk_file, &keys.ksk)?;]);
+
+        let manifest = KeyManifest {
+            params:     params.clone(),
+            params_fp:  params.fingerprint(),
+            sk_file:    KeyStore::file_name(& sk_file),
+            bsk_file:   KeyStore::file_name(&bsk_file),
+            ksk_file:   KeyStore::file_name(&ksk_file),
+        };
+        fs::write(&manifest_file, serde_json::to_string_pretty(&manifest)?)?;
+
+        Ok(())
+    }
+
+    /// Fail if any key file for `params` is present (in either format)
+    fn check_no_files(&self, params: &Params) -> Result<(), Box<dyn Error>> {
+        let (sk_file, bsk_file, ksk_file, manifest_file) = self.paths_from_params(params);
+        let mut files = vec![sk_file, bsk_file, ksk_file, manifest_file];
+        files.extend(self.legacy_paths_from_params(params));
+
+        let present: Vec<String> = files.iter().filter(|f| f.exists()).map(|f| f.display().to_string()).collect();
+        if !present.is_empty() {
+            return Err(format!("Key files already present ({}), refusing to overwrite them.", present.join(", ")).into());
+        }
+        Ok(())
+    }
+
+    /// Get paths of key files & manifest from params
+    /// * key files have the `.bin` extension, so that they never collide with legacy `.key` files
+    fn paths_from_params(&self, par: &Params) -> (PathBuf, PathBuf, PathBuf, PathBuf) {
+        let suffix = KeyStore::suffix_from_params(par);
+        let       sk_file = self.dir.join(format!(      "SK__{}.bin",  suffix));
+        let       bk_file = self.dir.join(format!(      "BK__{}.bin",  suffix));
+        let      ksk_file = self.dir.join(format!(     "KSK__{}.bin",  suffix));
+        let manifest_file = self.dir.join(format!("MANIFEST__{}.json", suffix));
+
+        (sk_file, bk_file, ksk_file, manifest_file)
+    }
+
+    /// Get paths of legacy key files from params: `[sk, bsk, ksk]`
+    fn legacy_paths_from_params(&self, par: &Params) -> [PathBuf; 3] {
+        let suffix = KeyStore::suffix_from_params(par);
+        [
+            self.dir.join(format!( "SK__{}.key", suffix)),
+            self.dir.join(format!( "BK__{}.key", suffix)),
+            self.dir.join(format!("KSK__{}.key", suffix)),
+        ]
+    }
+
+    fn suffix_from_params(par: &Params) -> String {
+        format!("n-{}_N-{}_gamma-{}_l-{}_kappa-{}_t-{}",
+                    par.lwe_params.dimension,
+                         par.rlwe_params.polynomial_size,
+                                  par.bs_base_log,
+                                       par.bs_level,
+                                                par.ks_base_log,
+                                                     par.ks_level,
+        )
+    }
+
+    fn file_name(path: &Path) -> String {
+        path.file_name().map(|f| f.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    fn write_key<K: Serialize>(path: &Path, key: &K) -> Result<(), Box<dyn Error>> {
+        let bytes = bincode::serialize(key)?;
+        fs::write(path, bytes).map_err(|e| format!("Cannot write key file {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    fn read_key<K: serde::de::DeserializeOwned>(path: &Path) -> Result<K, Box<dyn Error>> {
+        let bytes = fs::read(path).map_err(|e| format!("Cannot read key file {}: {}", path.display(), e))?;
+        Ok(bincode::deserialize(&bytes).map_err(|e| format!("Corrupted key file {}: {}", path.display(), e))?)
+    }
+}